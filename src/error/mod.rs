@@ -0,0 +1,418 @@
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    Scheme,
+    HierPart,
+    Authority,
+    Userinfo,
+    Host,
+    Port,
+    Path,
+    Query,
+    Fragment,
+    Uri,
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Component::Scheme => write!(f, "scheme"),
+            Component::HierPart => write!(f, "hier part"),
+            Component::Authority => write!(f, "authority"),
+            Component::Userinfo => write!(f, "userinfo"),
+            Component::Host => write!(f, "host"),
+            Component::Port => write!(f, "port"),
+            Component::Path => write!(f, "path"),
+            Component::Query => write!(f, "query"),
+            Component::Fragment => write!(f, "fragment"),
+            Component::Uri => write!(f, "URI"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UriErrorKind {
+    InvalidScheme,
+    MissingScheme,
+    UnknownScheme,
+    InvalidHierPart,
+    InvalidAuthority,
+    InvalidUserinfo,
+    InvalidIpv6Address,
+    InvalidIpvFuture,
+    InvalidZoneId,
+    UnterminatedIpLiteral,
+    InvalidPort,
+    PortOverflow,
+    InvalidPath,
+    InvalidQuery,
+    InvalidFragment,
+    InvalidUri,
+    BadPercentEscape,
+}
+
+impl fmt::Display for UriErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UriErrorKind::InvalidScheme => {
+                write!(f, "Invalid scheme")
+            }
+            UriErrorKind::MissingScheme => {
+                write!(f, "Missing scheme")
+            }
+            UriErrorKind::UnknownScheme => {
+                write!(f, "Unknown scheme")
+            }
+            UriErrorKind::InvalidHierPart => {
+                write!(f, "Invalid hier part")
+            }
+            UriErrorKind::InvalidAuthority => {
+                write!(f, "Invalid authority")
+            }
+            UriErrorKind::InvalidUserinfo => {
+                write!(f, "Invalid userinfo")
+            }
+            UriErrorKind::InvalidIpv6Address => {
+                write!(f, "Invalid IPv6 address")
+            }
+            UriErrorKind::InvalidIpvFuture => {
+                write!(f, "Invalid IPvFuture")
+            }
+            UriErrorKind::InvalidZoneId => {
+                write!(f, "Invalid zone ID")
+            }
+            UriErrorKind::UnterminatedIpLiteral => {
+                write!(f, "Unterminated IP literal")
+            }
+            UriErrorKind::InvalidPort => {
+                write!(f, "Invalid port")
+            }
+            UriErrorKind::PortOverflow => {
+                write!(f, "Port overflow")
+            }
+            UriErrorKind::InvalidPath => {
+                write!(f, "Invalid path")
+            }
+            UriErrorKind::InvalidQuery => {
+                write!(f, "Invalid query")
+            }
+            UriErrorKind::InvalidFragment => {
+                write!(f, "Invalid fragment")
+            }
+            UriErrorKind::InvalidUri => {
+                write!(f, "Invalid URI")
+            }
+            UriErrorKind::BadPercentEscape => {
+                write!(f, "Bad percent escape")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UriError {
+    kind: UriErrorKind,
+    component: Component,
+    offset: usize,
+    byte: Option<u8>,
+    expected: &'static str,
+}
+
+impl UriError {
+    #[inline]
+    pub(crate) const fn new(
+        kind: UriErrorKind,
+        component: Component,
+        expected: &'static str,
+    ) -> Self {
+        Self {
+            kind,
+            component,
+            offset: 0,
+            byte: None,
+            expected,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn at(mut self, input: &[u8], offset: usize) -> Self {
+        self.offset = offset;
+        self.byte = input.get(offset).copied();
+        self
+    }
+
+    #[inline]
+    pub(crate) fn or_bad_percent_escape(mut self) -> Self {
+        if self.byte == Some(0x25) {
+            self.kind = UriErrorKind::BadPercentEscape;
+            self.expected = "\"%\" HEXDIG HEXDIG";
+        }
+        self
+    }
+
+    #[inline]
+    pub(crate) fn shift(mut self, offset: usize) -> Self {
+        self.offset += offset;
+        self
+    }
+
+    #[inline]
+    pub fn kind(&self) -> UriErrorKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    pub fn byte(&self) -> Option<u8> {
+        self.byte
+    }
+
+    #[inline]
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    #[inline]
+    pub fn display_with<'a, T: AsRef<[u8]> + ?Sized>(
+        &'a self,
+        input: &'a T,
+    ) -> UriErrorDisplay<'a> {
+        UriErrorDisplay {
+            error: self,
+            input: input.as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for UriError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}: ", self.kind, self.offset)?;
+        match self.byte {
+            Some(byte) if byte == 0x20 || byte.is_ascii_graphic() => {
+                write!(f, "unexpected '{}'", char::from(byte))?;
+            }
+            Some(byte) => {
+                write!(f, "unexpected byte 0x{:02x}", byte)?;
+            }
+            None => {
+                write!(f, "unexpected end of input")?;
+            }
+        }
+        write!(f, ", expected {}.", self.expected)
+    }
+}
+
+impl std::error::Error for UriError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UriErrorDisplay<'a> {
+    error: &'a UriError,
+    input: &'a [u8],
+}
+
+impl fmt::Display for UriErrorDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.error)?;
+        for &byte in self.input {
+            if byte == 0x20 || byte.is_ascii_graphic() {
+                f.write_char(char::from(byte))?;
+            } else {
+                f.write_char('?')?;
+            }
+        }
+        f.write_char('\n')?;
+        for _ in 0..self.error.offset {
+            f.write_char(' ')?;
+        }
+        f.write_char('^')
+    }
+}
+
+#[cfg(test)]
+mod tests_uri_error {
+    use crate::{Component, Uri, UriErrorKind};
+
+    #[test]
+    fn test_position() {
+        let error = Uri::parse("http://exa mple.com/").unwrap_err();
+        assert_eq!(error.kind(), UriErrorKind::InvalidAuthority);
+        assert_eq!(error.component(), Component::Authority);
+        assert_eq!(error.offset(), 10);
+        assert_eq!(error.byte(), Some(b' '));
+
+        let error = Uri::parse("http://example.com/over/%zz").unwrap_err();
+        assert_eq!(error.kind(), UriErrorKind::BadPercentEscape);
+        assert_eq!(error.component(), Component::Path);
+        assert_eq!(error.offset(), 24);
+        assert_eq!(error.byte(), Some(b'%'));
+
+        let error = Uri::parse("http://[::1/").unwrap_err();
+        assert_eq!(error.kind(), UriErrorKind::UnterminatedIpLiteral);
+        assert_eq!(error.component(), Component::Host);
+        assert_eq!(error.offset(), 11);
+        assert_eq!(error.byte(), Some(b'/'));
+
+        let error = Uri::parse("http").unwrap_err();
+        assert_eq!(error.kind(), UriErrorKind::InvalidScheme);
+        assert_eq!(error.offset(), 4);
+        assert_eq!(error.byte(), None);
+    }
+
+    #[test]
+    fn test_kinds() {
+        for (string, kind, component, offset) in &[
+            (
+                "1http://a/",
+                UriErrorKind::InvalidScheme,
+                Component::Scheme,
+                0,
+            ),
+            (
+                "http://us er@a/",
+                UriErrorKind::InvalidUserinfo,
+                Component::Userinfo,
+                9,
+            ),
+            (
+                "http://us%2@a/",
+                UriErrorKind::BadPercentEscape,
+                Component::Userinfo,
+                9,
+            ),
+            (
+                "http://a%zz/",
+                UriErrorKind::BadPercentEscape,
+                Component::Authority,
+                8,
+            ),
+            (
+                "http://[::1",
+                UriErrorKind::UnterminatedIpLiteral,
+                Component::Host,
+                11,
+            ),
+            (
+                "http://[::g]/",
+                UriErrorKind::InvalidIpv6Address,
+                Component::Host,
+                10,
+            ),
+            (
+                "http://[1::2::3]/",
+                UriErrorKind::InvalidIpv6Address,
+                Component::Host,
+                8,
+            ),
+            (
+                "http://[v1]/",
+                UriErrorKind::InvalidIpvFuture,
+                Component::Host,
+                10,
+            ),
+            (
+                "http://[fe80::1%eth0]/",
+                UriErrorKind::InvalidZoneId,
+                Component::Host,
+                15,
+            ),
+            (
+                "http://[fe80::1%25]/",
+                UriErrorKind::InvalidZoneId,
+                Component::Host,
+                18,
+            ),
+            (
+                "http://[fe80::1%25e%zz]/",
+                UriErrorKind::BadPercentEscape,
+                Component::Host,
+                19,
+            ),
+            (
+                "http://a:80x/",
+                UriErrorKind::InvalidPort,
+                Component::Port,
+                11,
+            ),
+            (
+                "http://a:65536/",
+                UriErrorKind::PortOverflow,
+                Component::Port,
+                9,
+            ),
+            (
+                "http://a/b c",
+                UriErrorKind::InvalidPath,
+                Component::Path,
+                10,
+            ),
+            (
+                "http://a/?b c",
+                UriErrorKind::InvalidQuery,
+                Component::Query,
+                11,
+            ),
+            (
+                "http://a/?b%",
+                UriErrorKind::BadPercentEscape,
+                Component::Query,
+                11,
+            ),
+            (
+                "http://a/#b#c",
+                UriErrorKind::InvalidFragment,
+                Component::Fragment,
+                11,
+            ),
+        ] {
+            let error = Uri::parse(string).unwrap_err();
+            assert_eq!(
+                (error.kind(), error.component(), error.offset()),
+                (*kind, *component, *offset),
+                "{}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let error = Uri::parse("http://exa mple.com/").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid authority at byte 10: unexpected ' ', expected \":\", \"/\", \"?\", \"#\" or end of authority."
+        );
+
+        let error = Uri::parse("http").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid scheme at byte 4: unexpected end of input, expected ALPHA, DIGIT, \"+\", \"-\", \".\" or \":\"."
+        );
+    }
+
+    #[test]
+    fn test_display_with() {
+        let input = "http://exa mple.com/";
+        let error = Uri::parse(input).unwrap_err();
+        assert_eq!(
+            error.display_with(input).to_string(),
+            format!("{}\nhttp://exa mple.com/\n          ^", error)
+        );
+
+        let input = "http://é/\t";
+        let error = Uri::parse(input).unwrap_err();
+        assert_eq!(
+            error.display_with(input).to_string(),
+            format!("{}\nhttp://??/?\n       ^", error)
+        );
+    }
+}
@@ -1,34 +1,17 @@
+mod error;
 mod macros;
+pub mod percent;
+#[cfg(feature = "serde")]
+mod serialization;
 mod uri;
 mod utils;
 
-pub use uri::{Authority, Fragment, HierPart, Path, Query, Scheme, Uri, UriBuilder};
+pub use error::{Component, UriError, UriErrorDisplay, UriErrorKind};
+pub use uri::{
+    Authority, Fragment, HierPart, HierPartBuilder, Host, HostKind, KnownScheme, Pair, Pairs, Path,
+    PathKind, Port, Query, QueryBuilder, Scheme, Segment, Segments, Uri, UriBuilder, UriReference,
+    Userinfo,
+};
 
-#[derive(Debug)]
-pub enum UriError {
-    InvalidScheme,
-    UnknownScheme,
-    InvalidQuery,
-    InvalidUri,
-}
-
-impl std::fmt::Display for UriError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            UriError::InvalidScheme => {
-                write!(f, "Invalid scheme.")
-            }
-            UriError::UnknownScheme => {
-                write!(f, "Unknown scheme.")
-            }
-            UriError::InvalidQuery => {
-                write!(f, "Invalid query.")
-            }
-            UriError::InvalidUri => {
-                write!(f, "Invalid URI.")
-            }
-        }
-    }
-}
-
-impl std::error::Error for UriError {}
+#[cfg(feature = "serde")]
+pub use serialization::{structured, QueryDeserializeError};
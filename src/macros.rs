@@ -1,45 +1,60 @@
-// #[macro_export]
-// macro_rules! is_alpha {
-//     ($char: expr) => {
-//         (($char >= 0x41 && $char <= 0x5a) || ($char >= 0x61 && $char <= 0x7a))
-//     };
-// }
+#[macro_export]
+macro_rules! is_alpha {
+    ($char: expr) => {
+        matches!($char, 0x41..=0x5a | 0x61..=0x7a)
+    };
+}
 
-// #[macro_export]
-// macro_rules! is_digit {
-//     ($char: expr) => {
-//         ($char >= 0x30 && $char <= 0x39)
-//     };
-// }
+#[macro_export]
+macro_rules! is_digit {
+    ($char: expr) => {
+        matches!($char, 0x30..=0x39)
+    };
+}
 
-// #[macro_export]
-// macro_rules! is_unreserved {
-//     ($char: expr) => {
-//         ($crate::is_alpha!($char)
-//             || $crate::is_digit!($char)
-//             || $char == 0x2d
-//             || $char == 0x2e
-//             || $char == 0x5f
-//             || $char == 0x7e)
-//     };
-// }
+#[macro_export]
+macro_rules! is_unreserved {
+    ($char: expr) => {
+        ($crate::is_alpha!($char)
+            || $crate::is_digit!($char)
+            || $char == 0x2d
+            || $char == 0x2e
+            || $char == 0x5f
+            || $char == 0x7e)
+    };
+}
 
-// #[macro_export]
-// macro_rules! is_hexdig {
-//     ($char: expr) => {
-//         ($crate::is_digit!($char)
-//             || ($char >= 0x41 && $char <= 0x46)
-//             || ($char >= 0x61 && $char <= 0x66))
-//     };
-// }
+#[macro_export]
+macro_rules! is_hexdig {
+    ($char: expr) => {
+        matches!($char, 0x30..=0x39 | 0x41..=0x46 | 0x61..=0x66)
+    };
+}
 
-// #[macro_export]
-// macro_rules! is_sub_delims {
-//     ($char: expr) => {
-//         ($char == 0x21
-//             || $char == 0x24
-//             || ($char >= 0x26 && $char <= 0x2c)
-//             || $char == 0x3b
-//             || $char == 0x3d)
-//     };
-// }
+#[macro_export]
+macro_rules! is_sub_delims {
+    ($char: expr) => {
+        matches!($char, 0x21 | 0x24 | 0x26..=0x2c | 0x3b | 0x3d)
+    };
+}
+
+#[macro_export]
+macro_rules! is_scheme_char {
+    ($char: expr) => {
+        ($crate::is_alpha!($char)
+            || $crate::is_digit!($char)
+            || $char == 0x2b
+            || $char == 0x2d
+            || $char == 0x2e)
+    };
+}
+
+#[macro_export]
+macro_rules! is_pchar {
+    ($char: expr) => {
+        ($crate::is_unreserved!($char)
+            || $crate::is_sub_delims!($char)
+            || $char == 0x3a
+            || $char == 0x40)
+    };
+}
@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::utils::{hex_value, is_pct_encoded};
+use crate::{is_pchar, is_sub_delims, is_unreserved};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+#[derive(Clone, Copy)]
+pub struct EncodeSet {
+    allowed: fn(u8) -> bool,
+}
+
+impl EncodeSet {
+    #[inline]
+    pub const fn new(allowed: fn(u8) -> bool) -> Self {
+        Self { allowed }
+    }
+
+    #[inline]
+    pub fn contains(&self, char: u8) -> bool {
+        char == 0x25 || !(self.allowed)(char)
+    }
+}
+
+impl fmt::Debug for EncodeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EncodeSet").finish()
+    }
+}
+
+fn is_userinfo(char: u8) -> bool {
+    is_unreserved!(char) || is_sub_delims!(char) || char == 0x3a
+}
+
+fn is_reg_name(char: u8) -> bool {
+    is_unreserved!(char) || is_sub_delims!(char)
+}
+
+fn is_path_segment(char: u8) -> bool {
+    is_pchar!(char)
+}
+
+fn is_path(char: u8) -> bool {
+    is_pchar!(char) || char == 0x2f
+}
+
+fn is_query_or_fragment(char: u8) -> bool {
+    is_pchar!(char) || char == 0x2f || char == 0x3f
+}
+
+fn is_query_pair(char: u8) -> bool {
+    is_query_or_fragment(char) && char != 0x26 && char != 0x3d && char != 0x2b
+}
+
+fn is_form_urlencoded(char: u8) -> bool {
+    char.is_ascii_alphanumeric() || matches!(char, 0x20 | 0x2a | 0x2d | 0x2e | 0x5f)
+}
+
+pub const USERINFO: EncodeSet = EncodeSet::new(is_userinfo);
+pub const REG_NAME: EncodeSet = EncodeSet::new(is_reg_name);
+pub const PATH_SEGMENT: EncodeSet = EncodeSet::new(is_path_segment);
+pub const PATH: EncodeSet = EncodeSet::new(is_path);
+pub const QUERY: EncodeSet = EncodeSet::new(is_query_or_fragment);
+pub const QUERY_PAIR: EncodeSet = EncodeSet::new(is_query_pair);
+pub const FRAGMENT: EncodeSet = EncodeSet::new(is_query_or_fragment);
+pub const FORM_URLENCODED: EncodeSet = EncodeSet::new(is_form_urlencoded);
+
+pub fn encode(input: &[u8], encode_set: EncodeSet) -> Cow<'_, [u8]> {
+    let first = match input.iter().position(|&char| encode_set.contains(char)) {
+        Some(index) => index,
+        None => return Cow::Borrowed(input),
+    };
+    let mut output = Vec::with_capacity(input.len() + 8);
+    output.extend_from_slice(&input[..first]);
+    for &char in &input[first..] {
+        if encode_set.contains(char) {
+            output.push(0x25);
+            output.push(HEX_DIGITS[usize::from(char >> 4)]);
+            output.push(HEX_DIGITS[usize::from(char & 0x0f)]);
+        } else {
+            output.push(char);
+        }
+    }
+    Cow::Owned(output)
+}
+
+pub fn decode(input: &[u8]) -> Cow<'_, [u8]> {
+    let first = match (0..input.len()).find(|&index| is_pct_encoded(input, index, input.len())) {
+        Some(index) => index,
+        None => return Cow::Borrowed(input),
+    };
+    let mut output = Vec::with_capacity(input.len());
+    output.extend_from_slice(&input[..first]);
+    let mut index = first;
+    while index < input.len() {
+        if is_pct_encoded(input, index, input.len()) {
+            output.push((hex_value(input[index + 1]) << 4) | hex_value(input[index + 2]));
+            index += 3;
+        } else {
+            output.push(input[index]);
+            index += 1;
+        }
+    }
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests_percent {
+    use crate::percent::{self, EncodeSet};
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            percent::encode(b"a b/c?d#e", percent::PATH_SEGMENT),
+            Cow::Borrowed(&b"a%20b%2Fc%3Fd%23e"[..])
+        );
+        assert_eq!(
+            percent::encode(b"a b/c?d#e", percent::PATH),
+            Cow::Borrowed(&b"a%20b/c%3Fd%23e"[..])
+        );
+        assert_eq!(
+            percent::encode(b"a b/c?d#e", percent::QUERY),
+            Cow::Borrowed(&b"a%20b/c?d%23e"[..])
+        );
+        assert_eq!(
+            percent::encode(b"a=b&c+d?e", percent::QUERY_PAIR),
+            Cow::Borrowed(&b"a%3Db%26c%2Bd?e"[..])
+        );
+        assert_eq!(
+            percent::encode(b"a b*-._~+", percent::FORM_URLENCODED),
+            Cow::Borrowed(&b"a b*-._%7E%2B"[..])
+        );
+        assert_eq!(
+            percent::encode(b"user:p@ss", percent::USERINFO),
+            Cow::Borrowed(&b"user:p%40ss"[..])
+        );
+        assert_eq!(
+            percent::encode(b"ex:ample.com", percent::REG_NAME),
+            Cow::Borrowed(&b"ex%3Aample.com"[..])
+        );
+        assert_eq!(
+            percent::encode("100%/é".as_bytes(), percent::FRAGMENT),
+            Cow::Borrowed(&b"100%25/%C3%A9"[..])
+        );
+    }
+
+    #[test]
+    fn test_encode_borrowed() {
+        assert!(matches!(
+            percent::encode(b"over/there", percent::PATH),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_encode_custom_set() {
+        let encode_set = EncodeSet::new(|char| char.is_ascii_alphanumeric());
+        assert_eq!(
+            percent::encode(b"a-b", encode_set),
+            Cow::Borrowed(&b"a%2Db"[..])
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            percent::decode(b"a%20b%2fc%C3%A9"),
+            Cow::Borrowed("a b/cé".as_bytes())
+        );
+        assert_eq!(
+            percent::decode(b"100%25%zz%2"),
+            Cow::Borrowed(&b"100%%zz%2"[..])
+        );
+        assert!(matches!(percent::decode(b"over/there"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,172 @@
+use bytes::Bytes;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::fmt;
+use std::str;
+
+use crate::{Authority, Component, Fragment, Path, Query, Scheme, Uri, UriError, UriErrorKind};
+
+mod query;
+pub mod structured;
+
+pub use query::QueryDeserializeError;
+
+type ParseBytes<T> = fn(&Bytes, &mut usize, &usize) -> Result<T, UriError>;
+
+fn serialize_bytes<S: Serializer>(input: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    let string = str::from_utf8(input).map_err(ser::Error::custom)?;
+    serializer.serialize_str(string)
+}
+
+fn parse_all<T>(input: &str, parse_bytes: ParseBytes<T>, error: UriError) -> Result<T, UriError> {
+    let bytes = Bytes::copy_from_slice(input.as_bytes());
+    let mut index = 0;
+    let value = parse_bytes(&bytes, &mut index, &bytes.len())?;
+    if index != bytes.len() {
+        return Err(error.at(&bytes, index));
+    }
+    Ok(value)
+}
+
+struct ComponentVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, UriError>,
+}
+
+impl<'de, T> Visitor<'de> for ComponentVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, input: &str) -> Result<T, E> {
+        (self.parse)(input).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, input: &[u8]) -> Result<T, E> {
+        let input = str::from_utf8(input)
+            .map_err(|_| E::invalid_value(de::Unexpected::Bytes(input), &self))?;
+        self.visit_str(input)
+    }
+}
+
+macro_rules! impl_serde {
+    ($component:ident, $expecting:expr, $parse:expr) => {
+        impl Serialize for $component {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.origin, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $component {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(ComponentVisitor {
+                    expecting: $expecting,
+                    parse: $parse,
+                })
+            }
+        }
+    };
+}
+
+impl_serde!(Uri, "a URI string", Uri::parse);
+impl_serde!(Scheme, "a URI scheme string", |input| input.parse());
+impl_serde!(Authority, "a URI authority string", |input| {
+    parse_all(
+        input,
+        Authority::parse_bytes,
+        UriError::new(
+            UriErrorKind::InvalidAuthority,
+            Component::Authority,
+            "end of authority",
+        ),
+    )
+});
+impl_serde!(Path, "a URI path string", |input| {
+    parse_all(
+        input,
+        Path::parse_bytes,
+        UriError::new(UriErrorKind::InvalidPath, Component::Path, "end of path"),
+    )
+});
+impl_serde!(Query, "a URI query string", |input| {
+    parse_all(
+        input,
+        Query::parse_bytes,
+        UriError::new(UriErrorKind::InvalidQuery, Component::Query, "end of query"),
+    )
+});
+impl_serde!(Fragment, "a URI fragment string", |input| {
+    parse_all(
+        input,
+        Fragment::parse_bytes,
+        UriError::new(
+            UriErrorKind::InvalidFragment,
+            Component::Fragment,
+            "end of fragment",
+        ),
+    )
+});
+
+#[cfg(test)]
+mod tests_serialization {
+    use crate::{Authority, Fragment, Path, Query, Scheme, Uri};
+
+    #[test]
+    fn test_serialize() {
+        let uri = Uri::parse("foo://example.com:8042/over/there?name=ferret#nose").unwrap();
+        assert_eq!(
+            serde_json::to_string(&uri).unwrap(),
+            "\"foo://example.com:8042/over/there?name=ferret#nose\""
+        );
+        assert_eq!(serde_json::to_string(uri.scheme()).unwrap(), "\"foo\"");
+        assert_eq!(
+            serde_json::to_string(uri.authority().unwrap()).unwrap(),
+            "\"example.com:8042\""
+        );
+        assert_eq!(
+            serde_json::to_string(uri.path()).unwrap(),
+            "\"/over/there\""
+        );
+        assert_eq!(
+            serde_json::to_string(uri.query().unwrap()).unwrap(),
+            "\"?name=ferret\""
+        );
+        assert_eq!(
+            serde_json::to_string(uri.fragment().unwrap()).unwrap(),
+            "\"#nose\""
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let string = "foo://example.com:8042/over/there?name=ferret#nose";
+        let uri: Uri = serde_json::from_str(&format!("\"{}\"", string)).unwrap();
+        assert_eq!(uri, Uri::parse(string).unwrap());
+
+        let scheme: Scheme = serde_json::from_str("\"foo\"").unwrap();
+        assert_eq!(scheme, Scheme::from_slice(b"foo"));
+        let authority: Authority = serde_json::from_str("\"user@example.com:8042\"").unwrap();
+        assert_eq!(authority, Authority::from_slice(b"user@example.com:8042"));
+        let path: Path = serde_json::from_str("\"/over/there\"").unwrap();
+        assert_eq!(path, Path::from_slice(b"/over/there"));
+        let query: Query = serde_json::from_str("\"?name=ferret\"").unwrap();
+        assert_eq!(query, Query::from_slice(b"?name=ferret"));
+        let fragment: Fragment = serde_json::from_str("\"#nose\"").unwrap();
+        assert_eq!(fragment, Fragment::from_slice(b"#nose"));
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        let error = serde_json::from_str::<Uri>("\"http://exa mple.com/\"").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid"));
+        assert!(serde_json::from_str::<Uri>("\"/over/there\"").is_err());
+        assert!(serde_json::from_str::<Uri>("42").is_err());
+        assert!(serde_json::from_str::<Scheme>("\"foo:bar\"").is_err());
+        assert!(serde_json::from_str::<Authority>("\"example.com/over\"").is_err());
+        assert!(serde_json::from_str::<Path>("\"/over?there\"").is_err());
+        assert!(serde_json::from_str::<Query>("\"name=ferret\"").is_err());
+        assert!(serde_json::from_str::<Fragment>("\"#no se\"").is_err());
+    }
+}
@@ -0,0 +1,412 @@
+use serde::de::{
+    self, Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str;
+
+use crate::Query;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryDeserializeError {
+    key: Option<String>,
+    message: String,
+}
+
+impl QueryDeserializeError {
+    #[inline]
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn with_key(mut self, key: &str) -> Self {
+        if self.key.is_none() {
+            self.key = Some(key.to_owned());
+        }
+        self
+    }
+}
+
+impl fmt::Display for QueryDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "Invalid value for key `{}`: {}", key, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for QueryDeserializeError {}
+
+impl de::Error for QueryDeserializeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self {
+            key: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Query {
+    pub fn deserialize_into<'de, T: Deserialize<'de>>(
+        &'de self,
+    ) -> Result<T, QueryDeserializeError> {
+        let mut entries: Vec<Entry<'de>> = Vec::new();
+        let mut indices: HashMap<Cow<'de, [u8]>, usize> = HashMap::new();
+        for (key, value) in self.form_pairs() {
+            match indices.get(&key) {
+                Some(&index) => entries[index].1.push(value),
+                None => {
+                    indices.insert(key.clone(), entries.len());
+                    entries.push((key, vec![value]));
+                }
+            }
+        }
+        T::deserialize(QueryDeserializer(entries.into_iter()))
+    }
+}
+
+fn utf8(input: Cow<'_, [u8]>) -> Result<Cow<'_, str>, QueryDeserializeError> {
+    match input {
+        Cow::Borrowed(input) => str::from_utf8(input).map(Cow::Borrowed).ok(),
+        Cow::Owned(input) => String::from_utf8(input).map(Cow::Owned).ok(),
+    }
+    .ok_or_else(|| de::Error::custom("invalid UTF-8"))
+}
+
+type Entry<'de> = (Cow<'de, [u8]>, Vec<Cow<'de, [u8]>>);
+
+struct QueryDeserializer<'de>(std::vec::IntoIter<Entry<'de>>);
+
+impl<'de> de::Deserializer<'de> for QueryDeserializer<'de> {
+    type Error = QueryDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for QueryDeserializer<'de> {
+    type Error = QueryDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.0.as_slice().first() {
+            Some((key, _)) => seed
+                .deserialize(ValueDeserializer(key.clone()))
+                .map(Some)
+                .map_err(|error| error.with_key(&String::from_utf8_lossy(key))),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, values) = self
+            .0
+            .next()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(ValuesDeserializer(values))
+            .map_err(|error| error.with_key(&String::from_utf8_lossy(&key)))
+    }
+}
+
+struct ValuesDeserializer<'de>(Vec<Cow<'de, [u8]>>);
+
+impl<'de> ValuesDeserializer<'de> {
+    fn single(mut self) -> Result<ValueDeserializer<'de>, QueryDeserializeError> {
+        match self.0.len() {
+            1 => Ok(ValueDeserializer(self.0.remove(0))),
+            count => Err(de::Error::custom(format_args!(
+                "expected a single value, found {}",
+                count
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValuesDeserializer<'de> {
+    type Error = QueryDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.len() == 1 {
+            self.single()?.deserialize_any(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ValuesAccess(self.0.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_identifier
+    }
+
+    forward_to_deserialize_any! {
+        unit_struct tuple_struct map struct
+    }
+}
+
+struct ValuesAccess<'de>(std::vec::IntoIter<Cow<'de, [u8]>>);
+
+impl<'de> SeqAccess<'de> for ValuesAccess<'de> {
+    type Error = QueryDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.0.next() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct ValueDeserializer<'de>(Cow<'de, [u8]>);
+
+impl<'de> ValueDeserializer<'de> {
+    fn parse<T>(self) -> Result<T, QueryDeserializeError>
+    where
+        T: str::FromStr,
+        T::Err: fmt::Display,
+    {
+        utf8(self.0)?.parse().map_err(de::Error::custom)
+    }
+}
+
+macro_rules! deserialize_from_str {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = QueryDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match utf8(self.0)? {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Cow::Borrowed(value) => visitor.visit_borrowed_bytes(value),
+            Cow::Owned(value) => visitor.visit_byte_buf(value),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(utf8(self.0)?.into_owned().into_deserializer())
+    }
+
+    deserialize_from_str! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        str string unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests_query_deserialize {
+    use crate::Query;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Order {
+        Asc,
+        Desc,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Params {
+        page: u32,
+        tags: Vec<String>,
+        q: Option<String>,
+        order: Option<Order>,
+        #[serde(default)]
+        exact: bool,
+    }
+
+    #[test]
+    fn test_deserialize_into() {
+        let query = Query::from_slice(b"?page=2&tags=rust&q=uri+parser&tags=web%20dev&order=desc");
+        let params: Params = query.deserialize_into().unwrap();
+        assert_eq!(
+            params,
+            Params {
+                page: 2,
+                tags: vec![String::from("rust"), String::from("web dev")],
+                q: Some(String::from("uri parser")),
+                order: Some(Order::Desc),
+                exact: false,
+            }
+        );
+
+        let query = Query::from_slice(b"?page=1&tags=rust&exact=true&unknown=1");
+        let params: Params = query.deserialize_into().unwrap();
+        assert_eq!(params.tags, vec![String::from("rust")]);
+        assert_eq!(params.q, None);
+        assert!(params.exact);
+    }
+
+    #[test]
+    fn test_deserialize_into_borrowed() {
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            name: &'a str,
+        }
+
+        let query = Query::from_slice(b"?name=ferret");
+        let borrowed: Borrowed = query.deserialize_into().unwrap();
+        assert_eq!(borrowed.name, "ferret");
+
+        let map: HashMap<String, String> = query.deserialize_into().unwrap();
+        assert_eq!(map.get("name").map(String::as_str), Some("ferret"));
+    }
+
+    #[test]
+    fn test_deserialize_into_errors() {
+        let query = Query::from_slice(b"?page=two&tags=rust");
+        let error = query.deserialize_into::<Params>().unwrap_err();
+        assert_eq!(error.key(), Some("page"));
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for key `page`: invalid digit found in string"
+        );
+
+        let query = Query::from_slice(b"?page=1&page=2&tags=rust");
+        let error = query.deserialize_into::<Params>().unwrap_err();
+        assert_eq!(error.key(), Some("page"));
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for key `page`: expected a single value, found 2"
+        );
+
+        let query = Query::from_slice(b"?page=1&tags=rust&order=random");
+        let error = query.deserialize_into::<Params>().unwrap_err();
+        assert_eq!(error.key(), Some("order"));
+
+        let query = Query::from_slice(b"?tags=rust");
+        let error = query.deserialize_into::<Params>().unwrap_err();
+        assert_eq!(error.key(), None);
+        assert_eq!(error.to_string(), "missing field `page`");
+    }
+}
@@ -0,0 +1,143 @@
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::{Authority, Fragment, HierPartBuilder, Path, Query, Scheme, Uri, UriBuilder};
+
+const FIELDS: &[&str] = &["scheme", "authority", "path", "query", "fragment"];
+
+pub fn serialize<S: Serializer>(uri: &Uri, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("Uri", FIELDS.len())?;
+    state.serialize_field("scheme", uri.scheme())?;
+    state.serialize_field("authority", &uri.authority())?;
+    state.serialize_field("path", uri.path())?;
+    state.serialize_field("query", &uri.query())?;
+    state.serialize_field("fragment", &uri.fragment())?;
+    state.end()
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uri, D::Error> {
+    deserializer.deserialize_struct("Uri", FIELDS, StructuredVisitor)
+}
+
+struct StructuredVisitor;
+
+impl<'de> Visitor<'de> for StructuredVisitor {
+    type Value = Uri;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of URI components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Uri, A::Error> {
+        let mut scheme: Option<Scheme> = None;
+        let mut authority: Option<Option<Authority>> = None;
+        let mut path: Option<Path> = None;
+        let mut query: Option<Option<Query>> = None;
+        let mut fragment: Option<Option<Fragment>> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "scheme" if scheme.is_none() => scheme = Some(map.next_value()?),
+                "authority" if authority.is_none() => authority = Some(map.next_value()?),
+                "path" if path.is_none() => path = Some(map.next_value()?),
+                "query" if query.is_none() => query = Some(map.next_value()?),
+                "fragment" if fragment.is_none() => fragment = Some(map.next_value()?),
+                "scheme" | "authority" | "path" | "query" | "fragment" => {
+                    return Err(de::Error::custom(format_args!("duplicate field `{}`", key)));
+                }
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+        let scheme = scheme.ok_or_else(|| de::Error::missing_field("scheme"))?;
+
+        let mut hier_part_builder = HierPartBuilder::new();
+        if let Some(authority) = authority.flatten() {
+            hier_part_builder.authority(authority);
+        }
+        if let Some(path) = path {
+            hier_part_builder.path(path);
+        }
+        let hier_part = hier_part_builder.build().map_err(de::Error::custom)?;
+
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(scheme);
+        uri_builder.hier_part(hier_part);
+        if let Some(query) = query.flatten() {
+            uri_builder.query(query);
+        }
+        if let Some(fragment) = fragment.flatten() {
+            uri_builder.fragment(fragment);
+        }
+        uri_builder.build().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests_structured {
+    use crate::Uri;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::structured")]
+        uri: Uri,
+    }
+
+    #[test]
+    fn test_serialize() {
+        let config = Config {
+            uri: Uri::parse("foo://example.com:8042/over/there?name=ferret#nose").unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            concat!(
+                r#"{"uri":{"scheme":"foo","authority":"example.com:8042","#,
+                r##""path":"/over/there","query":"?name=ferret","fragment":"#nose"}}"##
+            )
+        );
+
+        let config = Config {
+            uri: Uri::parse("mailto:user@example.com").unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            concat!(
+                r#"{"uri":{"scheme":"mailto","authority":null,"#,
+                r#""path":"user@example.com","query":null,"fragment":null}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let config: Config = serde_json::from_str(
+            r#"{"uri":{"scheme":"foo","authority":"example.com:8042","path":"/over/there","query":"?name=ferret"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.uri,
+            Uri::parse("foo://example.com:8042/over/there?name=ferret").unwrap()
+        );
+
+        let config: Config =
+            serde_json::from_str(r#"{"uri":{"scheme":"urn","path":"example:animal"}}"#).unwrap();
+        assert_eq!(config.uri, Uri::parse("urn:example:animal").unwrap());
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        for string in &[
+            r#"{"uri":{"path":"/over/there"}}"#,
+            r#"{"uri":{"scheme":"foo","authority":"example.com","path":"over"}}"#,
+            r#"{"uri":{"scheme":"foo","path":"/over there"}}"#,
+            r#"{"uri":{"scheme":"foo","host":"example.com"}}"#,
+            r#"{"uri":{"scheme":"foo","scheme":"bar"}}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Config>(string).is_err(),
+                "{}",
+                string
+            );
+        }
+    }
+}
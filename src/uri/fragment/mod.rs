@@ -1,6 +1,7 @@
 use bytes::Bytes;
 
-use crate::UriError;
+use crate::utils::while_query_or_fragment;
+use crate::{Component, UriError, UriErrorKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Fragment {
@@ -24,15 +25,44 @@ impl Fragment {
         Self { origin: bytes }
     }
 
-    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
         let mut index = *start;
-        while index < *end {
-            index += 1;
+        if index >= *end || input[index] != 0x23 {
+            return Err(
+                UriError::new(UriErrorKind::InvalidFragment, Component::Fragment, "\"#\"")
+                    .at(input, index),
+            );
+        }
+        index += 1;
+        while_query_or_fragment(input, &mut index, end);
+        if index != *end {
+            return Err(UriError::new(
+                UriErrorKind::InvalidFragment,
+                Component::Fragment,
+                "pchar, \"/\" or \"?\"",
+            )
+            .at(input, index)
+            .or_bad_percent_escape());
         }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
         let value = Self::from_slice(&input[*start..index]);
         *start = index;
         Ok(value)
     }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
@@ -66,4 +96,15 @@ mod tests_fragment {
         assert_eq!(fragment.origin, Bytes::from_static(b"#nose"));
         assert_eq!(cursor, 50);
     }
+
+    #[test]
+    fn test_parse_invalid() {
+        let string = "#no#se";
+        let mut cursor = 0;
+        assert!(Fragment::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+
+        let string = "#nose%2";
+        let mut cursor = 0;
+        assert!(Fragment::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+    }
 }
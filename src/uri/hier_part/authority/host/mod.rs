@@ -1,7 +1,21 @@
 use bytes::Bytes;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::{self, FromStr};
+
+use crate::utils::{while_ip_v4_address, while_pct_encoded, while_reg_name};
+use crate::{is_hexdig, is_sub_delims, is_unreserved, Component, UriError, UriErrorKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostKind {
+    RegName(Bytes),
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    IpvFuture { version: Bytes, data: Bytes },
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Host {
+    pub kind: HostKind,
     pub origin: Bytes,
 }
 
@@ -12,60 +26,355 @@ impl Host {
     }
 
     #[inline]
+    pub fn kind(&self) -> &HostKind {
+        &self.kind
+    }
+
     pub fn from_bytes(input: Bytes) -> Self {
-        Self { origin: input }
+        let kind = if input.len() > 1 && input[0] == 0x5b && input[input.len() - 1] == 0x5d {
+            let literal = input.slice(1..input.len() - 1);
+            if matches!(literal.first(), Some(0x56) | Some(0x76)) {
+                let version_end = literal
+                    .iter()
+                    .position(|&char| char == 0x2e)
+                    .unwrap_or(literal.len());
+                HostKind::IpvFuture {
+                    version: literal.slice(1..version_end),
+                    data: literal.slice((version_end + 1).min(literal.len())..),
+                }
+            } else {
+                let address_end = literal
+                    .iter()
+                    .position(|&char| char == 0x25)
+                    .unwrap_or(literal.len());
+                match parse_addr::<Ipv6Addr>(&literal[..address_end]) {
+                    Some(address) => HostKind::Ipv6(address),
+                    None => HostKind::RegName(input.clone()),
+                }
+            }
+        } else {
+            let mut index = 0;
+            if while_ip_v4_address(&input, &mut index, &input.len()) && index == input.len() {
+                match parse_addr::<Ipv4Addr>(&input) {
+                    Some(address) => HostKind::Ipv4(address),
+                    None => HostKind::RegName(input.clone()),
+                }
+            } else {
+                HostKind::RegName(input.clone())
+            }
+        };
+        Self {
+            kind,
+            origin: input,
+        }
+    }
+
+    pub fn zone_id(&self) -> Option<Bytes> {
+        match self.kind {
+            HostKind::Ipv6(_) => {
+                let literal_end = self.origin.len() - 1;
+                let percent = self.origin[..literal_end]
+                    .iter()
+                    .position(|&char| char == 0x25)?;
+                let zone_id = self.origin[percent..literal_end].strip_prefix(b"%25")?;
+                if zone_id.is_empty() {
+                    return None;
+                }
+                Some(self.origin.slice(percent + 3..literal_end))
+            }
+            _ => None,
+        }
     }
 
     #[inline]
     pub fn from_slice(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self { origin: bytes }
+        Self::from_bytes(bytes)
+    }
+
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+        let mut index = *start;
+        if index < *end && input[index] == 0x5b {
+            index += 1;
+            let literal_end = match input[index..*end].iter().position(|&char| char == 0x5d) {
+                Some(position) => index + position,
+                None => {
+                    return Err(
+                        host_error(UriErrorKind::UnterminatedIpLiteral, "\"]\"").at(input, *end)
+                    )
+                }
+            };
+            if index < literal_end && (input[index] == 0x56 || input[index] == 0x76) {
+                scan_ip_v_future(input, &mut index, &literal_end)?;
+            } else {
+                scan_ip_v6_address(input, &mut index, &literal_end)?;
+            }
+            index += 1;
+        } else {
+            while_reg_name(input, &mut index, end);
+        }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_slice(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+#[inline]
+fn host_error(kind: UriErrorKind, expected: &'static str) -> UriError {
+    UriError::new(kind, Component::Host, expected)
+}
+
+fn parse_addr<T: FromStr>(input: &[u8]) -> Option<T> {
+    str::from_utf8(input).ok()?.parse().ok()
+}
+
+fn scan_ip_v6_address(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+    let address_end = input[*start..*end]
+        .iter()
+        .position(|&char| char == 0x25)
+        .map_or(*end, |position| *start + position);
+    let address = &input[*start..address_end];
+    if let Some(position) = address
+        .iter()
+        .position(|&char| !is_hexdig!(char) && char != 0x3a && char != 0x2e)
+    {
+        return Err(
+            host_error(UriErrorKind::InvalidIpv6Address, "HEXDIG, \":\" or \".\"")
+                .at(input, *start + position),
+        );
+    }
+    if parse_addr::<Ipv6Addr>(address).is_none() {
+        return Err(host_error(UriErrorKind::InvalidIpv6Address, "IPv6address").at(input, *start));
+    }
+    if address_end < *end {
+        if !input[address_end..*end].starts_with(b"%25") {
+            return Err(host_error(UriErrorKind::InvalidZoneId, "\"%25\"").at(input, address_end));
+        }
+        scan_zone_id(input, &mut (address_end + 3), end)?;
     }
+    *start = *end;
+    Ok(())
 }
 
-// use bytes::Bytes;
-// use std::error::Error;
-//
-// use crate::utils::{while_ip_v4_address, while_reg_name};
-//
-// #[derive(Debug, Clone, PartialEq)]
-// pub enum Host {
-//     Host(String),
-//     Ipv4Addr(String),
-// }
-//
-// pub fn parse_host(input: &[u8], start: &mut usize, end: &usize) -> Result<Host, Box<dyn Error>> {
-//     let mut index = *start;
-//
-//     if while_ip_v4_address(input, &mut index, end)? {
-//         let string = String::from_utf8(input[*start..index - 1].to_vec())?;
-//         *start = index;
-//         // let bytes = Bytes::from(input[*start..index - 1]));
-//         return Ok(Host::Ipv4Addr(string));
-//     }
-//
-//     if while_reg_name(input, &mut index, end)? {
-//         let string = String::from_utf8(input[*start..index].to_vec())?;
-//         *start = index;
-//         // let bytes = Bytes::from(&input[*start..index]);
-//         return Ok(Host::Host(string));
-//     }
-//
-//     Ok(Host::Host("".into()))
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use crate::uri::authority::host::{parse_host, Host};
-//
-//     #[test]
-//     fn valid_ip_v4_address() {
-//         let string = b"127.0.0.1:80";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//
-//         let host = parse_host(string, &mut cursor, &end).unwrap();
-//
-//         assert_eq!(host, Host::Ipv4Addr("127.0.0.1".into()));
-//     }
-// }
+fn scan_zone_id(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+    let mut index = *start;
+    while index < *end {
+        if is_unreserved!(input[index]) {
+            index += 1;
+        } else if !while_pct_encoded(input, &mut index, end) {
+            break;
+        }
+    }
+    if index == *start {
+        return Err(host_error(UriErrorKind::InvalidZoneId, "ZoneID")
+            .at(input, index)
+            .or_bad_percent_escape());
+    }
+    if index != *end {
+        return Err(host_error(
+            UriErrorKind::InvalidZoneId,
+            "unreserved, pct-encoded or \"]\"",
+        )
+        .at(input, index)
+        .or_bad_percent_escape());
+    }
+    *start = index;
+    Ok(())
+}
+
+fn scan_ip_v_future(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+    let mut index = *start + 1;
+    while index < *end && is_hexdig!(input[index]) {
+        index += 1;
+    }
+    if index == *start + 1 {
+        return Err(host_error(UriErrorKind::InvalidIpvFuture, "HEXDIG").at(input, index));
+    }
+    if index >= *end || input[index] != 0x2e {
+        return Err(host_error(UriErrorKind::InvalidIpvFuture, "HEXDIG or \".\"").at(input, index));
+    }
+    index += 1;
+    let data_start = index;
+    while index < *end
+        && (is_unreserved!(input[index]) || is_sub_delims!(input[index]) || input[index] == 0x3a)
+    {
+        index += 1;
+    }
+    if index == data_start {
+        return Err(host_error(
+            UriErrorKind::InvalidIpvFuture,
+            "unreserved, sub-delims or \":\"",
+        )
+        .at(input, index));
+    }
+    if index != *end {
+        return Err(host_error(
+            UriErrorKind::InvalidIpvFuture,
+            "unreserved, sub-delims, \":\" or \"]\"",
+        )
+        .at(input, index));
+    }
+    *start = index;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_host {
+    use crate::{Host, HostKind};
+    use bytes::Bytes;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_from_slice() {
+        let host = Host::from_slice(b"example.com");
+        assert_eq!(host.origin, Bytes::from_static(b"example.com"));
+        assert_eq!(
+            host.kind(),
+            &HostKind::RegName(Bytes::from_static(b"example.com"))
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let string = "example.com:8042";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(host.bytes(), Bytes::from_static(b"example.com"));
+        assert_eq!(cursor, 11);
+    }
+
+    #[test]
+    fn test_parse_ip_v4_address() {
+        let string = "127.0.0.1:80";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(host.kind(), &HostKind::Ipv4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(cursor, 9);
+    }
+
+    #[test]
+    fn test_parse_ip_v4_like_reg_name() {
+        for string in &["256.0.0.1", "127.0.0.01", "1.2.3", "1.2.3.4.5"] {
+            let mut cursor = 0;
+            let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+            assert_eq!(
+                host.kind(),
+                &HostKind::RegName(Bytes::copy_from_slice(string.as_bytes()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_ip_v6_address() {
+        let string = "[2001:db8::7]:8042";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(host.bytes(), Bytes::from_static(b"[2001:db8::7]"));
+        assert_eq!(
+            host.kind(),
+            &HostKind::Ipv6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 7))
+        );
+        assert_eq!(cursor, 13);
+
+        let string = "[::ffff:192.0.2.1]";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(
+            host.kind(),
+            &HostKind::Ipv6(Ipv4Addr::new(192, 0, 2, 1).to_ipv6_mapped())
+        );
+    }
+
+    #[test]
+    fn test_parse_ip_v6_address_zone_id() {
+        let string = "[fe80::1%25eth0]:8080";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(
+            host.kind(),
+            &HostKind::Ipv6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1))
+        );
+        assert_eq!(host.zone_id(), Some(Bytes::from_static(b"eth0")));
+        assert_eq!(host.bytes(), Bytes::from_static(b"[fe80::1%25eth0]"));
+        assert_eq!(cursor, 16);
+
+        let string = "[fe80::1%25en%2F1]";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(host.zone_id(), Some(Bytes::from_static(b"en%2F1")));
+
+        let host = Host::from_slice(b"[2001:db8::7]");
+        assert_eq!(host.zone_id(), None);
+
+        for string in &["[::1%]", "[::1%2]", "[::1%25]", "[::1%24eth0]"] {
+            assert_eq!(
+                Host::from_slice(string.as_bytes()).zone_id(),
+                None,
+                "{}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_zone_id() {
+        for string in &[
+            "[fe80::1%eth0]",
+            "[fe80::1%25]",
+            "[fe80::1%25eth 0]",
+            "[fe80::1%25eth%zz]",
+            "[fe80::1%2]",
+        ] {
+            let mut cursor = 0;
+            assert!(Host::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+            assert_eq!(cursor, 0);
+        }
+    }
+
+    #[test]
+    fn test_parse_ip_v_future() {
+        let string = "[v7.fe80::a+en1]";
+        let mut cursor = 0;
+        let host = Host::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(
+            host.kind(),
+            &HostKind::IpvFuture {
+                version: Bytes::from_static(b"7"),
+                data: Bytes::from_static(b"fe80::a+en1"),
+            }
+        );
+        assert_eq!(cursor, 16);
+    }
+
+    #[test]
+    fn test_parse_invalid_ip_literal() {
+        for string in &[
+            "[2001:db8::7:8042",
+            "[2001:db8:::7]",
+            "[1:2:3:4:5:6:7:8:9]",
+            "[::g]",
+            "[v.data]",
+            "[v7]",
+            "[v7.]",
+        ] {
+            let mut cursor = 0;
+            assert!(Host::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+            assert_eq!(cursor, 0);
+        }
+    }
+}
@@ -1,13 +1,20 @@
 use bytes::Bytes;
 
-use crate::UriError;
+use crate::{Component, Scheme, UriError, UriErrorKind};
 
 mod host;
+mod port;
+mod userinfo;
 
-pub use host::Host;
+pub use host::{Host, HostKind};
+pub use port::Port;
+pub use userinfo::Userinfo;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Authority {
+    pub userinfo: Option<Userinfo>,
+    pub host: Host,
+    pub port: Option<Port>,
     pub origin: Bytes,
 }
 
@@ -18,30 +25,120 @@ impl Authority {
     }
 
     #[inline]
+    pub fn userinfo(&self) -> Option<&Userinfo> {
+        self.userinfo.as_ref()
+    }
+
+    #[inline]
+    pub fn host(&self) -> &Host {
+        &self.host
+    }
+
+    #[inline]
+    pub fn port(&self) -> Option<&Port> {
+        self.port.as_ref()
+    }
+
+    pub fn port_or_default(&self, scheme: &Scheme) -> Option<u16> {
+        match &self.port {
+            Some(port) => port.value(),
+            None => scheme.default_port(),
+        }
+    }
+
     pub fn from_bytes(input: Bytes) -> Self {
-        Self { origin: input }
+        let host_start = input
+            .iter()
+            .position(|&char| char == 0x40)
+            .map_or(0, |index| index + 1);
+        let userinfo = if host_start > 0 {
+            Some(Userinfo::from_bytes(input.slice(..host_start - 1)))
+        } else {
+            None
+        };
+        let host_end = if input[host_start..].starts_with(b"[") {
+            input[host_start..]
+                .iter()
+                .position(|&char| char == 0x5d)
+                .map_or(input.len(), |index| host_start + index + 1)
+        } else {
+            input[host_start..]
+                .iter()
+                .rposition(|&char| char == 0x3a)
+                .map_or(input.len(), |index| host_start + index)
+        };
+        let port = if host_end + 1 < input.len() && input[host_end] == 0x3a {
+            Some(Port::from_bytes(input.slice(host_end + 1..)))
+        } else {
+            None
+        };
+        Self {
+            userinfo,
+            host: Host::from_bytes(input.slice(host_start..host_end)),
+            port,
+            origin: input,
+        }
     }
 
     #[inline]
     pub fn from_slice(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self { origin: bytes }
+        Self::from_bytes(bytes)
     }
 
-    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
         let mut index = *start;
-        while index < *end && input[index] != 0x2f {
+        let authority_end = input[index..*end]
+            .iter()
+            .position(|&char| char == 0x2f || char == 0x3f || char == 0x23)
+            .map_or(*end, |position| index + position);
+        if input[index..authority_end].contains(&0x40) {
+            Userinfo::scan(input, &mut index, &authority_end)?;
+            index += 1;
+        }
+        Host::scan(input, &mut index, &authority_end)?;
+        let error = if index < authority_end && input[index] == 0x3a {
             index += 1;
+            Port::scan(input, &mut index, &authority_end)?;
+            UriError::new(
+                UriErrorKind::InvalidPort,
+                Component::Port,
+                "DIGIT, \"/\", \"?\", \"#\" or end of authority",
+            )
+        } else {
+            UriError::new(
+                UriErrorKind::InvalidAuthority,
+                Component::Authority,
+                "\":\", \"/\", \"?\", \"#\" or end of authority",
+            )
+        };
+        if index != authority_end {
+            return Err(error.at(input, index).or_bad_percent_escape());
         }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
         let value = Self::from_slice(&input[*start..index]);
         *start = index;
         Ok(value)
     }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
 pub mod tests_authority {
-    use crate::Authority;
+    use crate::{Authority, Host, Port, Scheme, Userinfo};
     use bytes::Bytes;
 
     #[test]
@@ -70,4 +167,90 @@ pub mod tests_authority {
         assert_eq!(authority.bytes(), Bytes::from_static(b"example.com:8042"));
         assert_eq!(cursor, 22);
     }
+
+    #[test]
+    fn test_components() {
+        let authority = Authority::from_slice(b"user:password@example.com:8042");
+        assert_eq!(
+            authority.userinfo(),
+            Some(&Userinfo::from_slice(b"user:password"))
+        );
+        assert_eq!(authority.host(), &Host::from_slice(b"example.com"));
+        assert_eq!(authority.port(), Some(&Port::from_slice(b"8042")));
+
+        let authority = Authority::from_slice(b"example.com");
+        assert_eq!(authority.userinfo(), None);
+        assert_eq!(authority.host(), &Host::from_slice(b"example.com"));
+        assert_eq!(authority.port(), None);
+
+        let authority = Authority::from_slice(b"[2001:db8::7]:8042");
+        assert_eq!(authority.userinfo(), None);
+        assert_eq!(authority.host(), &Host::from_slice(b"[2001:db8::7]"));
+        assert_eq!(authority.port(), Some(&Port::from_slice(b"8042")));
+
+        let authority = Authority::from_slice(b"[2001:db8::7]");
+        assert_eq!(authority.host(), &Host::from_slice(b"[2001:db8::7]"));
+        assert_eq!(authority.port(), None);
+    }
+
+    #[test]
+    fn test_port_or_default() {
+        let authority = Authority::from_slice(b"example.com:8042");
+        assert_eq!(
+            authority.port_or_default(&Scheme::from_slice(b"http")),
+            Some(8042)
+        );
+
+        let authority = Authority::from_slice(b"example.com");
+        assert_eq!(
+            authority.port_or_default(&Scheme::from_slice(b"http")),
+            Some(80)
+        );
+        assert_eq!(
+            authority.port_or_default(&Scheme::from_slice(b"https")),
+            Some(443)
+        );
+        assert_eq!(authority.port_or_default(&Scheme::from_slice(b"foo")), None);
+
+        let authority = Authority::from_slice(b"example.com:99999");
+        assert_eq!(authority.port().map(Port::value), Some(None));
+        assert_eq!(
+            authority.port_or_default(&Scheme::from_slice(b"http")),
+            None
+        );
+
+        let authority = Authority::from_slice(b"example.com:");
+        assert_eq!(authority.port(), None);
+        assert_eq!(
+            authority.port_or_default(&Scheme::from_slice(b"ws")),
+            Some(80)
+        );
+    }
+
+    #[test]
+    fn test_parse_components() {
+        let string = "http://user@[::1]:80/";
+        let mut cursor = 7;
+        let authority = Authority::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(authority.userinfo(), Some(&Userinfo::from_slice(b"user")));
+        assert_eq!(authority.host(), &Host::from_slice(b"[::1]"));
+        assert_eq!(authority.port(), Some(&Port::from_slice(b"80")));
+        assert_eq!(cursor, 20);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for string in &[
+            "exa mple.com/",
+            "a@b@example.com/",
+            "example.com:80:80/",
+            "example.com:8o/",
+            "example.com:99999/",
+            "[::1/",
+        ] {
+            let mut cursor = 0;
+            assert!(Authority::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+            assert_eq!(cursor, 0);
+        }
+    }
 }
@@ -1,107 +1,159 @@
-use std::error::Error;
-use std::fmt;
+use bytes::Bytes;
 
-use crate::utils::while_number;
-use crate::Scheme;
+use crate::utils::while_digit;
+use crate::{is_digit, Component, UriError, UriErrorKind};
 
-#[derive(Debug, Clone)]
-pub enum PortError {
-    PortNotExists,
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    pub value: Option<u16>,
+    pub origin: Bytes,
 }
 
-impl fmt::Display for PortError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            PortError::PortNotExists => write!(f, "Port not exists."),
+impl Port {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.origin.clone()
+    }
+
+    #[inline]
+    pub fn value(&self) -> Option<u16> {
+        self.value
+    }
+
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        let value = if input.is_empty() {
+            None
+        } else {
+            parse_value(&input)
+        };
+        Self {
+            value,
+            origin: input,
         }
     }
-}
 
-impl Error for PortError {}
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes(bytes)
+    }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Port {
-    Http,
-    Https,
-    Port(String),
-}
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+        let mut index = *start;
+        while_digit(input, &mut index, end);
+        if parse_value(&input[*start..index]).is_none() {
+            return Err(UriError::new(
+                UriErrorKind::PortOverflow,
+                Component::Port,
+                "a port number no greater than 65535",
+            )
+            .at(input, *start));
+        }
+        *start = index;
+        Ok(())
+    }
 
-impl From<Port> for Scheme {
-    fn from(port: Port) -> Scheme {
-        match port {
-            Port::Http => Scheme::Http,
-            Port::Https => Scheme::Https,
-            _ => panic!("cast port {:?} to scheme", port),
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        if index == *start {
+            return Err(
+                UriError::new(UriErrorKind::InvalidPort, Component::Port, "DIGIT").at(input, index),
+            );
         }
+        let value = Self::from_slice(&input[*start..index]);
+        *start = index;
+        Ok(value)
     }
-}
 
-impl ToString for Port {
-    fn to_string(&self) -> String {
-        match self {
-            Port::Http => String::from("80"),
-            Port::Https => String::from("443"),
-            Port::Port(port) => port.clone().to_string(),
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        if index == *start {
+            return Err(
+                UriError::new(UriErrorKind::InvalidPort, Component::Port, "DIGIT").at(input, index),
+            );
         }
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
     }
 }
 
-pub fn parse_port(
-    input: &[u8],
-    start: &mut usize,
-    end: &usize,
-) -> Result<Option<Port>, Box<dyn Error>> {
-    let mut index = *start;
+fn parse_value(input: &[u8]) -> Option<u16> {
+    input.iter().try_fold(0u16, |value, &char| {
+        if !is_digit!(char) {
+            return None;
+        }
+        value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(u16::from(char - 0x30)))
+    })
+}
 
-    if index > *end || input[index] != 0x3a {
-        return Ok(None);
+impl From<u16> for Port {
+    fn from(value: u16) -> Self {
+        Self {
+            value: Some(value),
+            origin: Bytes::from(value.to_string()),
+        }
     }
-
-    index += 1;
-
-    if !while_number(input, &mut index, end)? {
-        return Err(PortError::PortNotExists.into());
-    };
-
-    let string = String::from_utf8(input[*start + 1..index].to_vec())?;
-    let port = Port::Port(string);
-
-    *start = index;
-
-    return Ok(Some(port));
 }
 
 #[cfg(test)]
-mod test_port {
-    use crate::{Port, Scheme};
+mod tests_port {
+    use crate::Port;
+    use bytes::Bytes;
 
     #[test]
-    fn port_to_scheme() {
-        let port = Port::Http;
-        let scheme: Scheme = port.into();
-        assert_eq!(scheme, Scheme::Http);
+    fn test_from_slice() {
+        let port = Port::from_slice(b"8042");
+        assert_eq!(port.origin, Bytes::from_static(b"8042"));
+        assert_eq!(port.value(), Some(8042));
     }
 
     #[test]
-    fn port_to_string() {
-        let port = Port::Http;
-        assert_eq!(port.to_string(), "80");
+    fn test_from_slice_invalid() {
+        for string in &["99999", "65536", "8x", "-1", ""] {
+            let port = Port::from_slice(string.as_bytes());
+            assert_eq!(port.value(), None, "{}", string);
+            assert_eq!(port.bytes(), Bytes::copy_from_slice(string.as_bytes()));
+        }
     }
-}
 
-#[cfg(test)]
-mod test_parse_port {
-    use crate::{uri::authority::port::parse_port, Port};
+    #[test]
+    fn test_from_u16() {
+        let port = Port::from(8042);
+        assert_eq!(port, Port::from_slice(b"8042"));
+    }
 
     #[test]
-    fn valid() {
-        let string = b":80/path";
+    fn test_parse() {
+        let string = "8042/over/there";
         let mut cursor = 0;
-        let end = string.len() - 1;
+        let port = Port::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(port.bytes(), Bytes::from_static(b"8042"));
+        assert_eq!(port.value(), Some(8042));
+        assert_eq!(cursor, 4);
 
-        let port = parse_port(string, &mut cursor, &end).unwrap();
+        let string = "0080";
+        let mut cursor = 0;
+        let port = Port::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(port.value(), Some(80));
+    }
 
-        assert_eq!(port, Some(Port::Port("80".into())));
-        assert_eq!(cursor, 3);
+    #[test]
+    fn test_parse_overflow() {
+        for string in &["65536", "99999", "0000065536"] {
+            let mut cursor = 0;
+            assert!(Port::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+            assert_eq!(cursor, 0);
+        }
+
+        let string = "65535";
+        let mut cursor = 0;
+        let port = Port::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(port.value(), Some(65535));
     }
 }
@@ -1,51 +1,88 @@
-use std::error::Error;
+use bytes::Bytes;
 
-use crate::utils::while_pct_encoded;
-use crate::{is_sub_delims, is_unreserved};
+use crate::utils::while_userinfo;
+use crate::{Component, UriError, UriErrorKind};
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Userinfo {
-    Userinfo(String),
+pub struct Userinfo {
+    pub origin: Bytes,
 }
 
-pub fn parse_userinfo(
-    input: &[u8],
-    start: &mut usize,
-    end: &usize,
-) -> Result<Option<Userinfo>, Box<dyn Error>> {
-    let mut index = *start;
-
-    while index < *end
-        && (is_unreserved!(input[index])
-            || while_pct_encoded(input, &mut index, end)?
-            || is_sub_delims!(input[index])
-            || input[index] == 0x3a)
-    {
-        index += 1;
-    }
-
-    Ok(if input[index] == 0x40 {
-        let userinfo = Userinfo::Userinfo(String::from_utf8(input[*start..index].to_vec())?);
-        *start = index + 1;
-        Some(userinfo)
-    } else {
-        None
-    })
+impl Userinfo {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.origin.clone()
+    }
+
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self { origin: input }
+    }
+
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self { origin: bytes }
+    }
+
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+        let mut index = *start;
+        while_userinfo(input, &mut index, end);
+        if index >= *end || input[index] != 0x40 {
+            return Err(UriError::new(
+                UriErrorKind::InvalidUserinfo,
+                Component::Userinfo,
+                "unreserved, pct-encoded, sub-delims, \":\" or \"@\"",
+            )
+            .at(input, index)
+            .or_bad_percent_escape());
+        }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_slice(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
-mod test {
-    use crate::uri::authority::userinfo::{parse_userinfo, Userinfo};
+mod tests_userinfo {
+    use crate::Userinfo;
+    use bytes::Bytes;
 
     #[test]
-    fn parse_userinfo_1() {
-        let string = b"user:password@";
-        let end = string.len() - 1;
-        let mut cursor = 0;
+    fn test_from_slice() {
+        let userinfo = Userinfo::from_slice(b"user:password");
+        assert_eq!(userinfo.origin, Bytes::from_static(b"user:password"));
+    }
 
-        let userinfo = parse_userinfo(string, &mut cursor, &end).unwrap();
+    #[test]
+    fn test_parse() {
+        let string = "user:password@example.com";
+        let mut cursor = 0;
+        let userinfo = Userinfo::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(userinfo.bytes(), Bytes::from_static(b"user:password"));
+        assert_eq!(cursor, 13);
+    }
 
-        assert_eq!(userinfo, Some(Userinfo::Userinfo("user:password".into())));
-        assert_eq!(cursor, 14);
+    #[test]
+    fn test_parse_invalid() {
+        let string = "us er@example.com";
+        let mut cursor = 0;
+        assert!(Userinfo::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+        assert_eq!(cursor, 0);
     }
 }
@@ -1,13 +1,17 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 
-use crate::UriError;
+use crate::{Component, UriError, UriErrorKind};
 
 mod authority;
 mod path;
 
-pub use authority::Authority;
-pub use authority::Host;
-pub use path::Path;
+pub use authority::{Authority, Host, HostKind, Port, Userinfo};
+pub(crate) use path::remove_dot_segments;
+pub use path::{Path, PathKind, Segment, Segments};
+
+static EMPTY_PATH: Path = Path {
+    origin: Bytes::from_static(b""),
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct HierPart {
@@ -22,39 +26,85 @@ impl HierPart {
         self.origin.clone()
     }
 
+    #[inline]
+    pub fn authority(&self) -> Option<&Authority> {
+        self.authority.as_ref()
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        self.path.as_ref().unwrap_or(&EMPTY_PATH)
+    }
+
     #[inline]
     pub fn from_bytes(input: Bytes) -> Self {
-        Self {
-            authority: None,
-            path: None,
-            origin: input,
+        if input.starts_with(b"//") {
+            let authority_end = input[2..]
+                .iter()
+                .position(|&char| char == 0x2f)
+                .map_or(input.len(), |index| index + 2);
+            Self {
+                authority: Some(Authority::from_bytes(input.slice(2..authority_end))),
+                path: Some(Path::from_bytes(input.slice(authority_end..))),
+                origin: input,
+            }
+        } else {
+            Self {
+                authority: None,
+                path: Some(Path::from_bytes(input.clone())),
+                origin: input,
+            }
         }
     }
 
     #[inline]
     pub fn from_slice(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self {
-            authority: None,
-            path: None,
-            origin: bytes,
+        Self::from_bytes(bytes)
+    }
+
+    pub fn path_kind(&self) -> PathKind {
+        if self.authority.is_some() {
+            return PathKind::AbEmpty;
+        }
+        match &self.path {
+            Some(path) if path.origin.starts_with(b"/") => PathKind::Absolute,
+            Some(path) if !path.origin.is_empty() => PathKind::Rootless,
+            _ => PathKind::Empty,
         }
     }
 
-    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
         let mut index = *start;
-        while index < *end && input[index] != 0x3f && input[index] != 0x23 {
-            index += 1;
+        if input[index..*end].starts_with(b"//") {
+            index += 2;
+            Authority::scan(input, &mut index, end)?;
         }
+        Path::scan(input, &mut index, end)?;
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
         let value = Self::from_slice(&input[*start..index]);
         *start = index;
         Ok(value)
     }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
 }
 
 #[cfg(test)]
 mod tests_hier_part {
-    use crate::HierPart;
+    use crate::{Authority, HierPart, Path, PathKind};
     use bytes::Bytes;
 
     #[test]
@@ -95,9 +145,81 @@ mod tests_hier_part {
         );
         assert_eq!(cursor, 33);
     }
+
+    #[test]
+    fn test_parse_decomposition() {
+        let string = "foo://example.com:8042/over/there?name=ferret#nose";
+        let mut cursor = 4;
+        let hier_part = HierPart::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(
+            hier_part.authority,
+            Some(Authority::from_slice(b"example.com:8042"))
+        );
+        assert_eq!(hier_part.path, Some(Path::from_slice(b"/over/there")));
+        assert_eq!(hier_part.path_kind(), PathKind::AbEmpty);
+
+        let origin = hier_part.origin.as_ptr() as usize;
+        let authority = hier_part.authority.unwrap().origin.as_ptr() as usize;
+        let path = hier_part.path.unwrap().origin.as_ptr() as usize;
+        assert_eq!(authority, origin + 2);
+        assert_eq!(path, origin + 18);
+    }
+
+    #[test]
+    fn test_path_kind() {
+        let hier_part = HierPart::from_slice(b"//example.com");
+        assert_eq!(
+            hier_part.authority,
+            Some(Authority::from_slice(b"example.com"))
+        );
+        assert_eq!(hier_part.path, Some(Path::from_slice(b"")));
+        assert_eq!(hier_part.path_kind(), PathKind::AbEmpty);
+
+        let hier_part = HierPart::from_slice(b"/over/there");
+        assert_eq!(hier_part.authority, None);
+        assert_eq!(hier_part.path_kind(), PathKind::Absolute);
+
+        let hier_part = HierPart::from_slice(b"user@example.com");
+        assert_eq!(hier_part.authority, None);
+        assert_eq!(hier_part.path_kind(), PathKind::Rootless);
+
+        let hier_part = HierPart::from_slice(b"");
+        assert_eq!(hier_part.authority, None);
+        assert_eq!(hier_part.path_kind(), PathKind::Empty);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let hier_part = HierPart::from_slice(b"//example.com/over/there");
+        assert_eq!(
+            hier_part.authority(),
+            Some(&Authority::from_slice(b"example.com"))
+        );
+        assert_eq!(hier_part.path(), &Path::from_slice(b"/over/there"));
+
+        let hier_part = HierPart {
+            authority: None,
+            path: None,
+            origin: Bytes::new(),
+        };
+        assert_eq!(hier_part.authority(), None);
+        assert_eq!(hier_part.path(), &Path::from_slice(b""));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let string = "http://exa mple.com/";
+        let mut cursor = 5;
+        assert!(HierPart::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+        assert_eq!(cursor, 5);
+
+        let string = "http://example.com/%zz";
+        let mut cursor = 5;
+        assert!(HierPart::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct HierPartBuilder {
     pub authority: Option<Authority>,
     pub path: Option<Path>,
@@ -122,27 +244,132 @@ impl HierPartBuilder {
         self
     }
 
-    pub fn build(&self) -> HierPart {
+    pub fn build(&self) -> Result<HierPart, UriError> {
+        let mut bytes = BytesMut::new();
         match (&self.authority, &self.path) {
-            (Some(authority), Some(path)) => {
-                let origin = Bytes::new();
-                HierPart {
-                    authority: self.authority.clone(),
-                    path: self.path.clone(),
-                    origin,
+            (Some(authority), path) => {
+                if let Some(path) = path {
+                    if !path.origin.is_empty() && !path.origin.starts_with(b"/") {
+                        return Err(UriError::new(
+                            UriErrorKind::InvalidPath,
+                            Component::Path,
+                            "\"/\" or end of path",
+                        )
+                        .at(&path.origin, 0));
+                    }
                 }
+                bytes.put(&b"//"[..]);
+                bytes.put(authority.bytes());
             }
             (None, Some(path)) => {
-                let origin = path.origin.clone();
-                HierPart {
-                    authority: None,
-                    path: self.path.clone(),
-                    origin,
+                if path.origin.starts_with(b"//") {
+                    return Err(UriError::new(
+                        UriErrorKind::InvalidPath,
+                        Component::Path,
+                        "segment-nz",
+                    )
+                    .at(&path.origin, 1));
                 }
             }
-            _ => {
-                panic!("");
-            }
+            (None, None) => {}
+        }
+        if let Some(path) = &self.path {
+            bytes.put(path.bytes());
+        }
+        let bytes = bytes.freeze();
+
+        let mut index = 0;
+        HierPart::scan(&bytes, &mut index, &bytes.len())?;
+        if index != bytes.len() {
+            return Err(UriError::new(
+                UriErrorKind::InvalidHierPart,
+                Component::HierPart,
+                "end of hier part",
+            )
+            .at(&bytes, index));
         }
+
+        Ok(HierPart::from_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests_hier_part_builder {
+    use crate::{Authority, HierPart, HierPartBuilder, Path, PathKind, UriErrorKind};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build() {
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.authority(Authority::from_slice(b"example.com:8042"));
+        hier_part_builder.path(Path::from_slice(b"/over/there"));
+        let hier_part = hier_part_builder.build().unwrap();
+        assert_eq!(
+            hier_part.bytes(),
+            Bytes::from_static(b"//example.com:8042/over/there")
+        );
+        assert_eq!(
+            hier_part,
+            HierPart::from_slice(b"//example.com:8042/over/there")
+        );
+    }
+
+    #[test]
+    fn test_build_authority_only() {
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.authority(Authority::from_slice(b"example.com"));
+        let hier_part = hier_part_builder.build().unwrap();
+        assert_eq!(hier_part.bytes(), Bytes::from_static(b"//example.com"));
+        assert_eq!(hier_part.path, Some(Path::from_slice(b"")));
+        assert_eq!(hier_part.path_kind(), PathKind::AbEmpty);
+    }
+
+    #[test]
+    fn test_build_path_only() {
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.path(Path::from_slice(b"user@example.com"));
+        let hier_part = hier_part_builder.build().unwrap();
+        assert_eq!(hier_part.bytes(), Bytes::from_static(b"user@example.com"));
+        assert_eq!(hier_part.authority, None);
+        assert_eq!(hier_part.path_kind(), PathKind::Rootless);
+    }
+
+    #[test]
+    fn test_build_empty() {
+        let hier_part = HierPartBuilder::new().build().unwrap();
+        assert_eq!(hier_part.bytes(), Bytes::new());
+        assert_eq!(hier_part.path_kind(), PathKind::Empty);
+    }
+
+    #[test]
+    fn test_build_invalid_path() {
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.authority(Authority::from_slice(b"example.com"));
+        hier_part_builder.path(Path::from_slice(b"over/there"));
+        assert!(matches!(
+            hier_part_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::InvalidPath && error.offset() == 0
+        ));
+
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.path(Path::from_slice(b"//over/there"));
+        assert!(matches!(
+            hier_part_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::InvalidPath && error.offset() == 1
+        ));
+
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.path(Path::from_slice(b"/over?there"));
+        assert!(hier_part_builder.build().is_err());
+    }
+
+    #[test]
+    fn test_build_invalid_authority() {
+        let mut hier_part_builder = HierPartBuilder::new();
+        hier_part_builder.authority(Authority::from_slice(b"exa mple.com"));
+        assert!(matches!(
+            hier_part_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::InvalidAuthority
+        ));
     }
 }
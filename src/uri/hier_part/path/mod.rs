@@ -1,4 +1,22 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::borrow::Cow;
+
+use crate::percent;
+use crate::utils::while_path;
+use crate::{Component, UriError, UriErrorKind};
+
+mod segment;
+
+pub use segment::{Segment, Segments};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathKind {
+    AbEmpty,
+    Absolute,
+    NoScheme,
+    Rootless,
+    Empty,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Path {
@@ -21,11 +39,152 @@ impl Path {
         let bytes = Bytes::copy_from_slice(input);
         Self { origin: bytes }
     }
+
+    #[inline]
+    pub fn is_absolute(&self) -> bool {
+        self.origin.starts_with(b"/")
+    }
+
+    #[inline]
+    pub fn has_trailing_slash(&self) -> bool {
+        self.origin.ends_with(b"/")
+    }
+
+    #[inline]
+    pub fn segments(&self) -> Segments {
+        Segments::new(self.origin.clone())
+    }
+
+    pub fn push(&mut self, segment: &[u8]) -> Result<(), UriError> {
+        let segment = encode_segment(segment)?;
+        let mut bytes = BytesMut::with_capacity(self.origin.len() + segment.len() + 1);
+        bytes.put(&self.origin[..]);
+        if &self.origin[..] != b"/" {
+            bytes.put_u8(0x2f);
+        }
+        bytes.put(&segment[..]);
+        self.origin = bytes.freeze();
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<Segment> {
+        if self.origin.is_empty() || &self.origin[..] == b"/" {
+            return None;
+        }
+        let (segment, origin) = match self.origin.iter().rposition(|&char| char == 0x2f) {
+            Some(0) => (self.origin.slice(1..), self.origin.slice(..1)),
+            Some(index) => (self.origin.slice(index + 1..), self.origin.slice(..index)),
+            None => (self.origin.clone(), Bytes::new()),
+        };
+        self.origin = origin;
+        Some(Segment::from_bytes(segment))
+    }
+
+    pub fn set_last(&mut self, segment: &[u8]) -> Result<(), UriError> {
+        let last_segment_start = match self.origin.iter().rposition(|&char| char == 0x2f) {
+            Some(index) => index + 1,
+            None if self.origin.is_empty() => return self.push(segment),
+            None => 0,
+        };
+        let segment = encode_segment(segment)?;
+        let mut bytes = BytesMut::with_capacity(last_segment_start + segment.len());
+        bytes.put(&self.origin[..last_segment_start]);
+        bytes.put(&segment[..]);
+        self.origin = bytes.freeze();
+        Ok(())
+    }
+
+    /// Removes literal `.` and `..` segments as in RFC 3986 section 5.2.4.
+    ///
+    /// Percent-encoded dot segments such as `%2E%2E` are left in place, so
+    /// decode unreserved characters first (e.g. with `Uri::normalize`) before
+    /// relying on the result to filter path traversal.
+    #[inline]
+    pub fn remove_dot_segments(&self) -> Path {
+        Self::from_bytes(Bytes::from(remove_dot_segments(&self.origin)))
+    }
+
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
+        let mut index = *start;
+        while_path(input, &mut index, end);
+        if index < *end && input[index] != 0x3f && input[index] != 0x23 {
+            return Err(UriError::new(
+                UriErrorKind::InvalidPath,
+                Component::Path,
+                "pchar, \"/\", \"?\" or \"#\"",
+            )
+            .at(input, index)
+            .or_bad_percent_escape());
+        }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_slice(&input[*start..index]);
+        *start = index;
+        Ok(value)
+    }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+fn encode_segment(segment: &[u8]) -> Result<Cow<'_, [u8]>, UriError> {
+    if segment == b"." || segment == b".." {
+        return Err(UriError::new(
+            UriErrorKind::InvalidPath,
+            Component::Path,
+            "a segment other than \".\" or \"..\"",
+        )
+        .at(segment, 0));
+    }
+    Ok(percent::encode(segment, percent::PATH_SEGMENT))
+}
+
+pub(crate) fn remove_dot_segments(mut input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len());
+    while !input.is_empty() {
+        if input.starts_with(b"../") {
+            input = &input[3..];
+        } else if input.starts_with(b"./") || input.starts_with(b"/./") {
+            input = &input[2..];
+        } else if input == b"/." {
+            output.push(0x2f);
+            input = &[];
+        } else if input.starts_with(b"/../") || input == b"/.." {
+            let last_segment_start = output.iter().rposition(|&char| char == 0x2f).unwrap_or(0);
+            output.truncate(last_segment_start);
+            if input == b"/.." {
+                output.push(0x2f);
+                input = &[];
+            } else {
+                input = &input[3..];
+            }
+        } else if input == b"." || input == b".." {
+            input = &[];
+        } else {
+            let segment_end = input[1..]
+                .iter()
+                .position(|&char| char == 0x2f)
+                .map_or(input.len(), |index| index + 1);
+            output.extend_from_slice(&input[..segment_end]);
+            input = &input[segment_end..];
+        }
+    }
+    output
 }
 
 #[cfg(test)]
 mod tests_path {
-    use crate::Path;
+    use crate::{Path, Segment, Uri, UriErrorKind};
     use bytes::Bytes;
 
     #[test]
@@ -45,68 +204,185 @@ mod tests_path {
         let path = Path::from_slice(b"/over/there");
         assert_eq!(path.origin, Bytes::from_static(b"/over/there"));
     }
-}
 
-// use std::error::Error;
-//
-// use crate::is_unreserved;
-//
-// #[derive(Debug, Clone, PartialEq)]
-// pub enum Path {
-//     Path(String),
-//     PathEmpty,
-// }
-//
-// pub fn parse_path(input: &[u8], start: &mut usize, end: &usize) -> Result<Path, Box<dyn Error>> {
-//     let mut index = *start;
-//
-//     while index <= *end && input[index] == 0x2f {
-//         index += 1;
-//         while index <= *end && is_unreserved!(input[index]) {
-//             index += 1;
-//         }
-//     }
-//
-//     let string = String::from_utf8(input[*start..index].to_vec())?;
-//     let path = Path::Path(string);
-//
-//     *start = index;
-//
-//     Ok(path)
-// }
-//
-// #[cfg(test)]
-// mod test_path {
-//
-//     use crate::uri::path::{parse_path, Path};
-//
-//     #[test]
-//     fn test_parse_path_1() {
-//         use parse_path;
-//         use Path;
-//
-//         let s = b"/path/to/file";
-//         let l = s.len() - 1;
-//         let mut c = 0;
-//
-//         let p = parse_path(s, &mut c, &l).unwrap();
-//
-//         assert_eq!(p, Path::Path(String::from("/path/to/file")));
-//         assert_eq!(c, 13);
-//     }
-//
-//     #[test]
-//     fn test_parse_path_2() {
-//         use parse_path;
-//         use Path;
-//
-//         let s = b"/path/to/file?v=1";
-//         let l = s.len() - 1;
-//         let mut c = 0;
-//
-//         let p = parse_path(s, &mut c, &l).unwrap();
-//
-//         assert_eq!(p, Path::Path(String::from("/path/to/file")));
-//         assert_eq!(c, 13);
-//     }
-// }
+    #[test]
+    fn test_parse() {
+        let string = "foo://example.com:8042/over/there?name=ferret#nose";
+        let mut cursor = 22;
+        let path = Path::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over/there"));
+        assert_eq!(cursor, 33);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let string = "/over/th ere";
+        let mut cursor = 0;
+        assert!(Path::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn test_segments() {
+        let path = Path::from_slice(b"/over/th%20ere/");
+        let segments: Vec<Segment> = path.segments().collect();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::from_slice(b"over"),
+                Segment::from_slice(b"th%20ere"),
+                Segment::from_slice(b""),
+            ]
+        );
+        assert_eq!(&segments[1].decode()[..], b"th ere");
+
+        let segments: Vec<Segment> = Path::from_slice(b"a/b").segments().collect();
+        assert_eq!(
+            segments,
+            vec![Segment::from_slice(b"a"), Segment::from_slice(b"b")]
+        );
+
+        let segments: Vec<Segment> = Path::from_slice(b"/").segments().collect();
+        assert_eq!(segments, vec![Segment::from_slice(b"")]);
+
+        assert_eq!(Path::from_slice(b"").segments().count(), 0);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let path = Path::from_slice(b"/over/there/");
+        assert!(path.is_absolute());
+        assert!(path.has_trailing_slash());
+
+        let path = Path::from_slice(b"over/there");
+        assert!(!path.is_absolute());
+        assert!(!path.has_trailing_slash());
+    }
+
+    #[test]
+    fn test_push() {
+        let mut path = Path::from_slice(b"/over");
+        path.push(b"th ere/now").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over/th%20ere%2Fnow"));
+
+        let mut path = Path::from_slice(b"/over/");
+        path.push(b"there").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over//there"));
+
+        let mut path = Path::from_slice(b"/");
+        path.push(b"over").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over"));
+
+        let mut path = Path::from_slice(b"");
+        path.push(b"over").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over"));
+    }
+
+    #[test]
+    fn test_push_empty_segment() {
+        let mut path = Path::from_slice(b"/a/");
+        path.push(b"").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/a//"));
+        assert_eq!(path.segments().count(), 3);
+
+        path.push(b"b").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/a///b"));
+        assert_eq!(path.segments().count(), 4);
+    }
+
+    #[test]
+    fn test_push_dot_segments() {
+        let mut path = Path::from_slice(b"/a/b");
+        for segment in &[&b"."[..], &b".."[..]] {
+            let error = path.push(segment).unwrap_err();
+            assert_eq!(error.kind(), UriErrorKind::InvalidPath);
+            assert!(path.set_last(segment).is_err());
+        }
+        assert_eq!(path.bytes(), Bytes::from_static(b"/a/b"));
+
+        path.push(b"...").unwrap();
+        path.set_last(b".hidden").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/a/b/.hidden"));
+        assert_eq!(path.remove_dot_segments(), path);
+    }
+
+    #[test]
+    fn test_pop() {
+        let mut path = Path::from_slice(b"/over/there");
+        assert_eq!(path.pop(), Some(Segment::from_slice(b"there")));
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over"));
+        assert_eq!(path.pop(), Some(Segment::from_slice(b"over")));
+        assert_eq!(path.bytes(), Bytes::from_static(b"/"));
+        assert_eq!(path.pop(), None);
+
+        let mut path = Path::from_slice(b"/over/");
+        assert_eq!(path.pop(), Some(Segment::from_slice(b"")));
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over"));
+
+        let mut path = Path::from_slice(b"over");
+        assert_eq!(path.pop(), Some(Segment::from_slice(b"over")));
+        assert_eq!(path.bytes(), Bytes::new());
+        assert_eq!(path.pop(), None);
+    }
+
+    #[test]
+    fn test_set_last() {
+        let mut path = Path::from_slice(b"/over/there");
+        path.set_last(b"here?").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over/here%3F"));
+
+        let mut path = Path::from_slice(b"/over/");
+        path.set_last(b"there").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/over/there"));
+
+        let mut path = Path::from_slice(b"over");
+        path.set_last(b"there").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"there"));
+
+        let mut path = Path::from_slice(b"");
+        path.set_last(b"there").unwrap();
+        assert_eq!(path.bytes(), Bytes::from_static(b"/there"));
+    }
+
+    #[test]
+    fn test_remove_encoded_dot_segments() {
+        let path = Path::from_slice(b"/a/%2E%2E/b");
+        assert_eq!(path.remove_dot_segments(), path);
+
+        let uri = Uri::parse("http://example.com/a/%2E%2E/b").unwrap();
+        assert_eq!(
+            uri.normalize().path().remove_dot_segments(),
+            Path::from_slice(b"/b")
+        );
+    }
+
+    #[test]
+    fn test_remove_dot_segments() {
+        for (path, result) in &[
+            ("/a/b/c/./../../g", "/a/g"),
+            ("mid/content=5/../6", "mid/6"),
+            ("/../a", "/a"),
+            ("/../../a/b", "/a/b"),
+            ("../a", "a"),
+            ("./../a", "a"),
+            ("/a/..", "/"),
+            ("/a/.", "/a/"),
+            ("/..", "/"),
+            ("/.", "/"),
+            ("..", ""),
+            (".", ""),
+            ("", ""),
+            ("/a/b/../../..", "/"),
+            ("/a/.b/..c/", "/a/.b/..c/"),
+            ("/a//../b", "/a/b"),
+            ("/a/%2E%2E/b", "/a/%2E%2E/b"),
+        ] {
+            assert_eq!(
+                Path::from_slice(path.as_bytes()).remove_dot_segments(),
+                Path::from_slice(result.as_bytes()),
+                "{}",
+                path
+            );
+        }
+    }
+}
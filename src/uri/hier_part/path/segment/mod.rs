@@ -0,0 +1,88 @@
+use bytes::Bytes;
+use std::borrow::Cow;
+
+use crate::percent;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub origin: Bytes,
+}
+
+impl Segment {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.origin.clone()
+    }
+
+    #[inline]
+    pub fn decode(&self) -> Cow<'_, [u8]> {
+        percent::decode(&self.origin)
+    }
+
+    #[inline]
+    pub fn from_bytes(input: Bytes) -> Self {
+        Self { origin: input }
+    }
+
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self { origin: bytes }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Segments {
+    origin: Bytes,
+    index: Option<usize>,
+}
+
+impl Segments {
+    pub(crate) fn new(origin: Bytes) -> Self {
+        let index = if origin.is_empty() {
+            None
+        } else if origin[0] == 0x2f {
+            Some(1)
+        } else {
+            Some(0)
+        };
+        Self { origin, index }
+    }
+}
+
+impl Iterator for Segments {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.index?;
+        let end = self.origin[start..]
+            .iter()
+            .position(|&char| char == 0x2f)
+            .map_or(self.origin.len(), |index| start + index);
+        self.index = if end < self.origin.len() {
+            Some(end + 1)
+        } else {
+            None
+        };
+        Some(Segment::from_bytes(self.origin.slice(start..end)))
+    }
+}
+
+#[cfg(test)]
+mod tests_segment {
+    use crate::Segment;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_from_slice() {
+        let segment = Segment::from_slice(b"over");
+        assert_eq!(segment.origin, Bytes::from_static(b"over"));
+    }
+
+    #[test]
+    fn test_decode() {
+        let segment = Segment::from_slice(b"a%20b%2Fc");
+        assert_eq!(segment.bytes(), Bytes::from_static(b"a%20b%2Fc"));
+        assert_eq!(&segment.decode()[..], b"a b/c");
+    }
+}
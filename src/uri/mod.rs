@@ -1,20 +1,33 @@
 use bytes::{BufMut, Bytes, BytesMut};
-use std::panic;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
 
 mod fragment;
 mod hier_part;
 mod query;
+mod reference;
 mod scheme;
 
 pub use fragment::Fragment;
-pub use hier_part::{Authority, HierPart, Path};
-pub use query::Query;
-pub use scheme::Scheme;
+pub(crate) use hier_part::remove_dot_segments;
+pub use hier_part::{
+    Authority, HierPart, HierPartBuilder, Host, HostKind, Path, PathKind, Port, Segment, Segments,
+    Userinfo,
+};
+pub use query::{Pair, Pairs, Query, QueryBuilder};
+pub use reference::UriReference;
+pub use scheme::{KnownScheme, Scheme};
 
-use crate::UriError;
+use crate::utils::normalize_pct_encoded;
+use crate::{Component, UriError, UriErrorKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Uri {
+    pub scheme: Scheme,
+    pub hier_part: HierPart,
+    pub query: Option<Query>,
+    pub fragment: Option<Fragment>,
     pub origin: Bytes,
 }
 
@@ -25,21 +38,200 @@ impl Uri {
     }
 
     #[inline]
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
+
+    #[inline]
+    pub fn authority(&self) -> Option<&Authority> {
+        self.hier_part.authority()
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        self.hier_part.path()
+    }
+
+    #[inline]
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+
+    #[inline]
+    pub fn fragment(&self) -> Option<&Fragment> {
+        self.fragment.as_ref()
+    }
+
     pub fn from_bytes(input: Bytes) -> Self {
-        Self { origin: input }
+        let scheme_end = input
+            .iter()
+            .position(|&char| char == 0x3a || char == 0x2f || char == 0x3f || char == 0x23)
+            .filter(|&index| input[index] == 0x3a)
+            .map_or(0, |index| index + 1);
+        let fragment_start = input[scheme_end..]
+            .iter()
+            .position(|&char| char == 0x23)
+            .map_or(input.len(), |index| scheme_end + index);
+        let query_start = input[scheme_end..fragment_start]
+            .iter()
+            .position(|&char| char == 0x3f)
+            .map_or(fragment_start, |index| scheme_end + index);
+        Self {
+            scheme: Scheme::from_bytes(input.slice(..scheme_end.saturating_sub(1))),
+            hier_part: HierPart::from_bytes(input.slice(scheme_end..query_start)),
+            query: if query_start < fragment_start {
+                Some(Query::from_bytes(input.slice(query_start..fragment_start)))
+            } else {
+                None
+            },
+            fragment: if fragment_start < input.len() {
+                Some(Fragment::from_bytes(input.slice(fragment_start..)))
+            } else {
+                None
+            },
+            origin: input,
+        }
     }
 
     #[inline]
     pub fn from_slice(input: &[u8]) -> Self {
         let bytes = Bytes::copy_from_slice(input);
-        Self { origin: bytes }
+        Self::from_bytes(bytes)
+    }
+
+    #[inline]
+    pub fn parse(input: &str) -> Result<Self, UriError> {
+        Self::parse_bytes(Bytes::copy_from_slice(input.as_bytes()))
+    }
+
+    pub fn parse_bytes(input: Bytes) -> Result<Self, UriError> {
+        let mut index = 0;
+        let uri_builder = UriBuilder::parse_bytes(&input, &mut index, &input.len())?;
+        Self::from_uri_builder(uri_builder, input)
+    }
+
+    pub fn normalize(&self) -> Uri {
+        let mut bytes = Vec::with_capacity(self.origin.len() + 1);
+        bytes.extend(self.scheme.origin.iter().map(u8::to_ascii_lowercase));
+        bytes.push(0x3a);
+        if let Some(authority) = self.authority() {
+            bytes.extend_from_slice(b"//");
+            if let Some(userinfo) = authority.userinfo() {
+                normalize_pct_encoded(&userinfo.origin, &mut bytes, false);
+                bytes.push(0x40);
+            }
+            let host = &authority.host().origin;
+            match host.iter().position(|&char| char == 0x25) {
+                Some(zone_start) if host.starts_with(b"[") => {
+                    bytes.extend(host[..zone_start].iter().map(u8::to_ascii_lowercase));
+                    normalize_pct_encoded(&host[zone_start..], &mut bytes, false);
+                }
+                _ => normalize_pct_encoded(host, &mut bytes, true),
+            }
+            if let Some(port) = authority.port() {
+                if port.value() != self.scheme.default_port() {
+                    bytes.push(0x3a);
+                    match port.value() {
+                        Some(value) => bytes.extend_from_slice(value.to_string().as_bytes()),
+                        None => bytes.extend_from_slice(&port.origin),
+                    }
+                }
+            }
+        }
+
+        let mut path = Vec::with_capacity(self.path().origin.len() + 1);
+        normalize_pct_encoded(&self.path().origin, &mut path, false);
+        if path.starts_with(b"/") {
+            path = remove_dot_segments(&path);
+        }
+        if path.is_empty() && self.authority().is_some() && self.scheme.default_port().is_some() {
+            path.push(0x2f);
+        }
+        if self.authority().is_none() && path.starts_with(b"//") {
+            bytes.extend_from_slice(b"/.");
+        }
+        bytes.extend_from_slice(&path);
+
+        if let Some(query) = self.query() {
+            normalize_pct_encoded(&query.origin, &mut bytes, false);
+        }
+        if let Some(fragment) = self.fragment() {
+            normalize_pct_encoded(&fragment.origin, &mut bytes, false);
+        }
+        Uri::from_bytes(Bytes::from(bytes))
+    }
+
+    pub fn equivalent(&self, other: &Uri) -> bool {
+        self.normalize().origin == other.normalize().origin
+    }
+
+    fn from_uri_builder(uri_builder: UriBuilder, origin: Bytes) -> Result<Self, UriError> {
+        Ok(Self {
+            scheme: uri_builder.scheme.ok_or_else(|| {
+                UriError::new(UriErrorKind::MissingScheme, Component::Scheme, "scheme")
+                    .at(&origin, 0)
+            })?,
+            hier_part: uri_builder
+                .hier_part
+                .unwrap_or_else(|| HierPart::from_bytes(Bytes::new())),
+            query: uri_builder.query,
+            fragment: uri_builder.fragment,
+            origin,
+        })
+    }
+}
+
+impl FromStr for Uri {
+    type Err = UriError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<&str> for Uri {
+    type Error = UriError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<String> for Uri {
+    type Error = UriError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        Self::parse_bytes(Bytes::from(input))
+    }
+}
+
+impl TryFrom<Bytes> for Uri {
+    type Error = UriError;
+
+    fn try_from(input: Bytes) -> Result<Self, Self::Error> {
+        Self::parse_bytes(input)
+    }
+}
+
+impl TryFrom<&[u8]> for Uri {
+    type Error = UriError;
+
+    fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse_bytes(Bytes::copy_from_slice(input))
+    }
+}
+
+impl fmt::Display for Uri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.origin))
     }
 }
 
 #[cfg(test)]
 mod tests_uri {
-    use crate::Uri;
+    use crate::{Authority, Fragment, HierPart, Path, Query, Scheme, Uri};
     use bytes::Bytes;
+    use std::convert::TryFrom;
 
     #[test]
     fn test_bytes() {
@@ -70,10 +262,160 @@ mod tests_uri {
             uri.origin,
             Bytes::from_static(b"foo://example.com:8042/over/there?name=ferret#nose")
         );
+        assert_eq!(uri.scheme, Scheme::from_slice(b"foo"));
+        assert_eq!(
+            uri.hier_part,
+            HierPart::from_slice(b"//example.com:8042/over/there")
+        );
+        assert_eq!(uri.query, Some(Query::from_slice(b"?name=ferret")));
+        assert_eq!(uri.fragment, Some(Fragment::from_slice(b"#nose")));
+
+        let uri = Uri::from_slice(b"urn:example:animal:ferret:nose");
+        assert_eq!(uri.scheme, Scheme::from_slice(b"urn"));
+        assert_eq!(
+            uri.hier_part,
+            HierPart::from_slice(b"example:animal:ferret:nose")
+        );
+        assert_eq!(uri.query, None);
+        assert_eq!(uri.fragment, None);
+
+        let uri = Uri::from_slice(b"http://a:99999/");
+        let authority = uri.authority().unwrap();
+        assert_eq!(authority.port().unwrap().value(), None);
+        assert_eq!(authority.port_or_default(uri.scheme()), None);
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let input = Bytes::from_static(b"foo://example.com:8042/over/there?name=ferret#nose");
+        let uri = Uri::parse_bytes(input.clone()).unwrap();
+        assert_eq!(uri, Uri::from_bytes(input.clone()));
+
+        let origin = input.as_ptr() as usize;
+        assert_eq!(uri.scheme.origin.as_ptr() as usize, origin);
+        assert_eq!(uri.hier_part.origin.as_ptr() as usize, origin + 4);
+        let authority = uri.hier_part.authority.as_ref().unwrap();
+        assert_eq!(authority.origin.as_ptr() as usize, origin + 6);
+        assert_eq!(authority.host.origin.as_ptr() as usize, origin + 6);
+        assert_eq!(
+            authority.port.as_ref().unwrap().origin.as_ptr() as usize,
+            origin + 18
+        );
+        assert_eq!(
+            uri.query.as_ref().unwrap().origin.as_ptr() as usize,
+            origin + 33
+        );
+        assert_eq!(
+            uri.fragment.as_ref().unwrap().origin.as_ptr() as usize,
+            origin + 45
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let uri = Uri::parse("foo://example.com:8042/over/there?name=ferret#nose").unwrap();
+        assert_eq!(uri.scheme(), &Scheme::from_slice(b"foo"));
+        assert_eq!(
+            uri.authority(),
+            Some(&Authority::from_slice(b"example.com:8042"))
+        );
+        assert_eq!(uri.path(), &Path::from_slice(b"/over/there"));
+        assert_eq!(uri.query(), Some(&Query::from_slice(b"?name=ferret")));
+        assert_eq!(uri.fragment(), Some(&Fragment::from_slice(b"#nose")));
+
+        let uri = Uri::parse("mailto:user@example.com").unwrap();
+        assert_eq!(uri.scheme(), &Scheme::from_slice(b"mailto"));
+        assert_eq!(uri.authority(), None);
+        assert_eq!(uri.path(), &Path::from_slice(b"user@example.com"));
+        assert_eq!(uri.query(), None);
+        assert_eq!(uri.fragment(), None);
+
+        assert!(Uri::parse("/over/there").is_err());
+
+        let uri = Uri::parse("HTTP://example.com/").unwrap();
+        assert_eq!(uri.scheme(), &Scheme::HTTP);
+        assert_eq!(uri.scheme().bytes(), Bytes::from_static(b"HTTP"));
+        assert_eq!(uri.to_string(), "HTTP://example.com/");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let uri: Uri = "http://example.com/".parse().unwrap();
+        assert_eq!(uri.bytes(), Bytes::from_static(b"http://example.com/"));
+        assert!("http://exa mple.com/".parse::<Uri>().is_err());
+    }
+
+    #[test]
+    fn test_try_from() {
+        let string = "http://example.com/over/there?name=ferret";
+        let reference = Uri::parse(string).unwrap();
+        assert_eq!(Uri::try_from(string).unwrap(), reference);
+        assert_eq!(Uri::try_from(String::from(string)).unwrap(), reference);
+        assert_eq!(Uri::try_from(Bytes::from(string)).unwrap(), reference);
+        assert_eq!(Uri::try_from(string.as_bytes()).unwrap(), reference);
+        assert!(Uri::try_from("http://example.com/%zz").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let string = "foo://user@[fe80::1%25eth0]:8042/over/there?name=ferret#nose";
+        let uri = Uri::parse(string).unwrap();
+        assert_eq!(uri.to_string(), string);
+    }
+
+    #[test]
+    fn test_normalize() {
+        for (string, normalized) in &[
+            ("HTTP://Example.COM:80/%7euser", "http://example.com/~user"),
+            ("http://example.com", "http://example.com/"),
+            ("http://example.com:/", "http://example.com/"),
+            ("https://example.com:0443/a", "https://example.com/a"),
+            ("https://example.com:8443/a", "https://example.com:8443/a"),
+            (
+                "eXAMPLE://a/./b/../b/%63/%7bfoo%7d",
+                "example://a/b/c/%7Bfoo%7D",
+            ),
+            ("foo://%55ser@EXAMPLE.com", "foo://User@example.com"),
+            ("http://[FE80::A%25Eth0]/", "http://[fe80::a%25Eth0]/"),
+            ("mailto:User@Example.COM", "mailto:User@Example.COM"),
+            ("http://a/b?%7e%2f#%7e%2f", "http://a/b?~%2F#~%2F"),
+        ] {
+            let uri = Uri::parse(string).unwrap().normalize();
+            assert_eq!(uri.to_string(), *normalized, "{}", string);
+            assert_eq!(uri, Uri::parse(normalized).unwrap(), "{}", string);
+            assert_eq!(uri.normalize(), uri, "{}", string);
+        }
+    }
+
+    #[test]
+    fn test_normalize_lenient() {
+        let uri = Uri::from_slice(b"HTTP://Exa mple.COM/%7e%zz").normalize();
+        assert_eq!(uri.to_string(), "http://exa mple.com/~%zz");
+        assert_eq!(uri.authority().unwrap().host().origin, "exa mple.com");
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let uri = Uri::parse("HTTP://Example.COM:80/%7euser").unwrap();
+        assert!(uri.equivalent(&Uri::parse("http://example.com/~user").unwrap()));
+        assert_ne!(uri, Uri::parse("http://example.com/~user").unwrap());
+
+        let uri = Uri::parse("example://a/b/c/%7Bfoo%7D").unwrap();
+        assert!(uri.equivalent(&Uri::parse("eXAMPLE://a/./b/../b/%63/%7bfoo%7d").unwrap()));
+
+        let uri = Uri::parse("http://example.com/a").unwrap();
+        assert!(!uri.equivalent(&Uri::parse("http://example.com/A").unwrap()));
+        assert!(!uri.equivalent(&Uri::parse("http://example.com:8080/a").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid() {
+        assert!(Uri::parse_bytes(Bytes::from_static(b"ht tp://example.com/")).is_err());
+        assert!(Uri::parse_bytes(Bytes::from_static(b"http://example.com/%zz")).is_err());
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct UriBuilder {
     pub scheme: Option<Scheme>,
     pub hier_part: Option<HierPart>,
@@ -113,17 +455,26 @@ impl UriBuilder {
     }
 
     pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let bytes = Bytes::copy_from_slice(&input[*start..*end]);
+        let mut index = 0;
+        let uri_builder = Self::parse_bytes(&bytes, &mut index, &bytes.len())
+            .map_err(|error| error.shift(*start))?;
+        *start += index;
+        Ok(uri_builder)
+    }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
         let mut index = *start;
         let mut uri_builder = Self::new();
-        uri_builder.scheme(Scheme::parse(input, &mut index, end)?);
-        uri_builder.hier_part(HierPart::parse(input, &mut index, end)?);
+        uri_builder.scheme(Scheme::parse_bytes(input, &mut index, end)?);
+        uri_builder.hier_part(HierPart::parse_bytes(input, &mut index, end)?);
         while index < *end {
             match input[index] {
                 0x3f => {
-                    uri_builder.query(Query::parse(input, &mut index, end)?);
+                    uri_builder.query(Query::parse_bytes(input, &mut index, end)?);
                 }
                 0x23 => {
-                    uri_builder.fragment(Fragment::parse(input, &mut index, end)?);
+                    uri_builder.fragment(Fragment::parse_bytes(input, &mut index, end)?);
                 }
                 _ => {
                     break;
@@ -131,7 +482,12 @@ impl UriBuilder {
             }
         }
         if index != *end {
-            return Err(UriError::InvalidUri);
+            return Err(UriError::new(
+                UriErrorKind::InvalidUri,
+                Component::Uri,
+                "\"?\", \"#\" or end of URI",
+            )
+            .at(input, index));
         }
         *start = index;
         Ok(uri_builder)
@@ -142,18 +498,66 @@ impl UriBuilder {
         match &self.scheme {
             Some(scheme) => {
                 bytes.put(scheme.bytes());
+                bytes.put_u8(0x3a);
             }
             None => {
-                panic!("")
+                return Err(UriError::new(
+                    UriErrorKind::MissingScheme,
+                    Component::Scheme,
+                    "scheme",
+                ));
+            }
+        }
+        if let Some(hier_part) = &self.hier_part {
+            bytes.put(hier_part.bytes());
+        }
+        if let Some(query) = &self.query {
+            bytes.put(query.bytes());
+        }
+        if let Some(fragment) = &self.fragment {
+            bytes.put(fragment.bytes());
+        }
+        let bytes = bytes.freeze();
+
+        let mut index = 0;
+        let uri_builder = Self::parse_bytes(&bytes, &mut index, &bytes.len())?;
+        let components = |uri_builder: &Self| {
+            [
+                uri_builder.scheme.as_ref().map(Scheme::bytes),
+                Some(
+                    uri_builder
+                        .hier_part
+                        .as_ref()
+                        .map_or_else(Bytes::new, HierPart::bytes),
+                ),
+                uri_builder.query.as_ref().map(Query::bytes),
+                uri_builder.fragment.as_ref().map(Fragment::bytes),
+            ]
+        };
+        let mut offset = 0;
+        for ((parsed, component), delimiter) in components(&uri_builder)
+            .iter()
+            .zip(&components(self))
+            .zip(&[1, 0, 0, 0])
+        {
+            if parsed != component {
+                return Err(UriError::new(
+                    UriErrorKind::InvalidUri,
+                    Component::Uri,
+                    "components that parse back unchanged",
+                )
+                .at(&bytes, offset));
             }
+            offset += component.as_ref().map_or(0, Bytes::len) + delimiter;
         }
-        Ok(Uri::from_bytes(bytes.freeze()))
+
+        Uri::from_uri_builder(uri_builder, bytes)
     }
 }
 
 #[cfg(test)]
 mod tests_uri_builder {
-    use crate::{Fragment, HierPart, Query, Scheme, UriBuilder};
+    use crate::{Fragment, HierPart, Query, Scheme, UriBuilder, UriErrorKind};
     use bytes::Bytes;
 
     #[test]
@@ -163,7 +567,7 @@ mod tests_uri_builder {
         let uri_builder = UriBuilder::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
 
         let mut reference_uri_builder = UriBuilder::new();
-        reference_uri_builder.scheme(Scheme::from_slice(b"foo:"));
+        reference_uri_builder.scheme(Scheme::from_slice(b"foo"));
         reference_uri_builder.hier_part(HierPart::from_slice(b"//example.com:8042/over/there"));
         reference_uri_builder.query(Query::from_slice(b"?name=ferret"));
         reference_uri_builder.fragment(Fragment::from_slice(b"#nose"));
@@ -172,12 +576,111 @@ mod tests_uri_builder {
         assert_eq!(cursor, 50);
     }
 
+    #[test]
+    fn test_parse_ip_v6_zone_id() {
+        let string = "http://[fe80::1%25eth0]:8080/";
+        let mut cursor = 0;
+        let uri_builder = UriBuilder::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        let authority = uri_builder.hier_part.unwrap().authority.unwrap();
+        assert_eq!(
+            authority.host().zone_id(),
+            Some(Bytes::from_static(b"eth0"))
+        );
+        assert_eq!(
+            authority.bytes(),
+            Bytes::from_static(b"[fe80::1%25eth0]:8080")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for string in &[
+            "ht tp://exa mple.com/%zz",
+            "http://exa mple.com/",
+            "http://example.com/%zz",
+            "http://example.com/?a=<b>",
+            "http://example.com/#a#b",
+            "//example.com/",
+        ] {
+            let mut cursor = 0;
+            assert!(UriBuilder::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+        }
+    }
+
     #[test]
     fn test_build() {
         let uri = UriBuilder::new()
-            .scheme(Scheme::from_slice(b"http:"))
+            .scheme(Scheme::from_slice(b"http"))
             .build()
             .unwrap();
         assert_eq!(uri.bytes(), Bytes::from_static(b"http:"));
     }
+
+    #[test]
+    fn test_build_all_components() {
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(Scheme::from_slice(b"foo"));
+        uri_builder.hier_part(HierPart::from_slice(b"//example.com:8042/over/there"));
+        uri_builder.query(Query::from_slice(b"?name=ferret"));
+        uri_builder.fragment(Fragment::from_slice(b"#nose"));
+        let uri = uri_builder.build().unwrap();
+        assert_eq!(
+            uri.bytes(),
+            Bytes::from_static(b"foo://example.com:8042/over/there?name=ferret#nose")
+        );
+
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(Scheme::from_slice(b"http"));
+        uri_builder.hier_part(HierPart::from_slice(b"//[fe80::1%25eth0]:8080/"));
+        uri_builder.fragment(Fragment::from_slice(b"#top"));
+        let uri = uri_builder.build().unwrap();
+        assert_eq!(
+            uri.bytes(),
+            Bytes::from_static(b"http://[fe80::1%25eth0]:8080/#top")
+        );
+    }
+
+    #[test]
+    fn test_build_round_trip() {
+        let string = "foo://user@example.com:8042/over/there?name=ferret#nose";
+        let mut cursor = 0;
+        let uri_builder = UriBuilder::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        let uri = uri_builder.build().unwrap();
+        assert_eq!(uri.bytes(), Bytes::from(string));
+    }
+
+    #[test]
+    fn test_build_without_scheme() {
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.hier_part(HierPart::from_slice(b"//example.com/"));
+        assert!(matches!(
+            uri_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::MissingScheme
+        ));
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(Scheme::from_slice(b"http"));
+        uri_builder.hier_part(HierPart::from_slice(b"//example.com/"));
+        uri_builder.query(Query::from_slice(b"name=ferret"));
+        assert!(matches!(
+            uri_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::InvalidUri && error.offset() == 5
+        ));
+
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(Scheme::from_slice(b"http"));
+        uri_builder.hier_part(HierPart::from_slice(b"//example.com/?"));
+        assert!(matches!(
+            uri_builder.build(),
+            Err(error) if error.kind() == UriErrorKind::InvalidUri && error.offset() == 5
+        ));
+
+        let mut uri_builder = UriBuilder::new();
+        uri_builder.scheme(Scheme::from_slice(b"http"));
+        uri_builder.hier_part(HierPart::from_slice(b"//exa mple.com/"));
+        assert!(uri_builder.build().is_err());
+    }
 }
@@ -1,6 +1,13 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::borrow::Cow;
 
-use crate::UriError;
+use crate::percent;
+use crate::utils::while_query_or_fragment;
+use crate::{Component, UriError, UriErrorKind};
+
+mod pairs;
+
+pub use pairs::{Pair, Pairs};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Query {
@@ -13,6 +20,59 @@ impl Query {
         self.origin.clone()
     }
 
+    #[inline]
+    pub fn pairs(&self) -> Pairs<'_> {
+        self.pairs_with_separators(b"&")
+    }
+
+    #[inline]
+    pub fn pairs_with_separators<'a>(&'a self, separators: &'a [u8]) -> Pairs<'a> {
+        let input = self.origin.strip_prefix(b"?").unwrap_or(&self.origin);
+        Pairs::new(input, separators)
+    }
+
+    #[inline]
+    pub fn form_pairs(&self) -> Pairs<'_> {
+        let input = self.origin.strip_prefix(b"?").unwrap_or(&self.origin);
+        Pairs::new_form(input)
+    }
+
+    pub fn from_form_pairs<I, K, V>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(0x3f);
+        for (key, value) in pairs {
+            if bytes.len() > 1 {
+                bytes.put_u8(0x26);
+            }
+            form_urlencode(key.as_ref(), &mut bytes);
+            bytes.put_u8(0x3d);
+            form_urlencode(value.as_ref(), &mut bytes);
+        }
+        Self::from_bytes(bytes.freeze())
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.pairs()
+            .find(|(pair_key, _)| &pair_key[..] == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_all(&self, key: &[u8]) -> Vec<Cow<'_, [u8]>> {
+        self.pairs()
+            .filter(|(pair_key, _)| &pair_key[..] == key)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.pairs().any(|(pair_key, _)| &pair_key[..] == key)
+    }
+
     #[inline]
     pub fn from_bytes(input: Bytes) -> Self {
         Self { origin: input }
@@ -24,21 +84,60 @@ impl Query {
         Self { origin: bytes }
     }
 
-    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
         let mut index = *start;
-        while index < *end && input[index] != 0x23 {
-            index += 1;
+        if index >= *end || input[index] != 0x3f {
+            return Err(
+                UriError::new(UriErrorKind::InvalidQuery, Component::Query, "\"?\"")
+                    .at(input, index),
+            );
+        }
+        index += 1;
+        while_query_or_fragment(input, &mut index, end);
+        if index < *end && input[index] != 0x23 {
+            return Err(UriError::new(
+                UriErrorKind::InvalidQuery,
+                Component::Query,
+                "pchar, \"/\", \"?\" or \"#\"",
+            )
+            .at(input, index)
+            .or_bad_percent_escape());
         }
+        *start = index;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
         let value = Self::from_slice(&input[*start..index]);
         *start = index;
         Ok(value)
     }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        *start = index;
+        Ok(value)
+    }
+}
+
+fn form_urlencode(input: &[u8], output: &mut BytesMut) {
+    let encoded = percent::encode(input, percent::FORM_URLENCODED);
+    output.extend(
+        encoded
+            .iter()
+            .map(|&char| if char == 0x20 { 0x2b } else { char }),
+    );
 }
 
 #[cfg(test)]
 mod tests_query {
-    use crate::Query;
+    use crate::{Pair, Query};
     use bytes::Bytes;
+    use std::borrow::Cow;
 
     #[test]
     fn test_bytes() {
@@ -66,4 +165,284 @@ mod tests_query {
         assert_eq!(query.bytes(), Bytes::from_static(b"?name=ferret"));
         assert_eq!(cursor, 45);
     }
+
+    #[test]
+    fn test_parse_invalid() {
+        let string = "?name=fer ret";
+        let mut cursor = 0;
+        assert!(Query::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+
+        let string = "?name=%zz";
+        let mut cursor = 0;
+        assert!(Query::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+    }
+
+    #[test]
+    fn test_pairs() {
+        let query = Query::from_slice(b"?name=ferret&color=&flag&name=weasel&caf%C3%A9=%26");
+        let pairs: Vec<Pair> = query.pairs().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::Borrowed(&b"name"[..]), Cow::Borrowed(&b"ferret"[..])),
+                (Cow::Borrowed(&b"color"[..]), Cow::Borrowed(&b""[..])),
+                (Cow::Borrowed(&b"flag"[..]), Cow::Borrowed(&b""[..])),
+                (Cow::Borrowed(&b"name"[..]), Cow::Borrowed(&b"weasel"[..])),
+                (Cow::Borrowed("café".as_bytes()), Cow::Borrowed(&b"&"[..])),
+            ]
+        );
+
+        assert_eq!(Query::from_slice(b"?").pairs().count(), 0);
+    }
+
+    #[test]
+    fn test_pairs_with_separators() {
+        let query = Query::from_slice(b"?a=1;b=2&c=3");
+        assert_eq!(query.pairs().count(), 2);
+        assert_eq!(query.pairs_with_separators(b"&;").count(), 3);
+    }
+
+    #[test]
+    fn test_get() {
+        let query = Query::from_slice(b"?name=ferret&flag&name=weasel&q=a%20b");
+        assert_eq!(query.get(b"name").as_deref(), Some(&b"ferret"[..]));
+        assert_eq!(query.get(b"flag").as_deref(), Some(&b""[..]));
+        assert_eq!(query.get(b"q").as_deref(), Some(&b"a b"[..]));
+        assert_eq!(query.get(b"missing"), None);
+        assert_eq!(
+            query.get_all(b"name"),
+            vec![Cow::Borrowed(&b"ferret"[..]), Cow::Borrowed(&b"weasel"[..])]
+        );
+        assert!(query.get_all(b"missing").is_empty());
+        assert!(query.contains_key(b"flag"));
+        assert!(!query.contains_key(b"missing"));
+    }
+
+    #[test]
+    fn test_form_pairs() {
+        let query = Query::from_slice(b"?q=rust+uri&lang=en%2Bus&empty=&flag");
+        let pairs: Vec<Pair> = query.form_pairs().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::Borrowed(&b"q"[..]), Cow::Borrowed(&b"rust uri"[..])),
+                (Cow::Borrowed(&b"lang"[..]), Cow::Borrowed(&b"en+us"[..])),
+                (Cow::Borrowed(&b"empty"[..]), Cow::Borrowed(&b""[..])),
+                (Cow::Borrowed(&b"flag"[..]), Cow::Borrowed(&b""[..])),
+            ]
+        );
+        assert_eq!(query.get(b"q").as_deref(), Some(&b"rust+uri"[..]));
+    }
+
+    #[test]
+    fn test_from_form_pairs() {
+        let query = Query::from_form_pairs(vec![
+            ("q", "rust uri"),
+            ("sym", "*-._~!'()&=+/?#%"),
+            ("", ""),
+            ("café", "ü"),
+        ]);
+        assert_eq!(
+            query.bytes(),
+            Bytes::from_static(
+                b"?q=rust+uri&sym=*-._%7E%21%27%28%29%26%3D%2B%2F%3F%23%25&=&caf%C3%A9=%C3%BC"
+            )
+        );
+        let mut cursor = 0;
+        assert_eq!(
+            Query::parse(&query.origin, &mut cursor, &query.origin.len()).unwrap(),
+            query
+        );
+
+        let pairs: Vec<Pair> = query.form_pairs().collect();
+        assert_eq!(
+            pairs[0],
+            (Cow::Borrowed(&b"q"[..]), Cow::Borrowed(&b"rust uri"[..]))
+        );
+        assert_eq!(pairs[1].1, Cow::Borrowed(&b"*-._~!'()&=+/?#%"[..]));
+        assert_eq!(pairs[3].0, Cow::Borrowed("café".as_bytes()));
+
+        let query = Query::from_form_pairs(Vec::<(&str, &str)>::new());
+        assert_eq!(query.bytes(), Bytes::from_static(b"?"));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueryBuilder {
+    pub pairs: Vec<Bytes>,
+}
+
+impl QueryBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self { pairs: Vec::new() }
+    }
+
+    pub fn from_query(query: &Query) -> Self {
+        let start = usize::from(query.origin.starts_with(b"?"));
+        let mut pairs = Vec::new();
+        let mut index = start;
+        for piece in query.origin[start..].split(|&char| char == 0x26) {
+            if !piece.is_empty() {
+                pairs.push(query.origin.slice(index..index + piece.len()));
+            }
+            index += piece.len() + 1;
+        }
+        Self { pairs }
+    }
+
+    pub fn append(&mut self, key: &[u8], value: &[u8]) -> &Self {
+        self.pairs.push(encode_pair(key, value));
+        self
+    }
+
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> &Self {
+        match self.pairs.iter().position(|pair| pair_key(pair) == key) {
+            Some(index) => {
+                let rest = self.pairs.split_off(index + 1);
+                self.pairs[index] = encode_pair(key, value);
+                self.pairs
+                    .extend(rest.into_iter().filter(|pair| pair_key(pair) != key));
+            }
+            None => self.pairs.push(encode_pair(key, value)),
+        }
+        self
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> &Self {
+        self.pairs.retain(|pair| pair_key(pair) != key);
+        self
+    }
+
+    pub fn retain<F>(&mut self, mut f: F) -> &Self
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        self.pairs
+            .retain(|pair| match Pairs::new(pair, b"").next() {
+                Some((key, value)) => f(&key, &value),
+                None => f(b"", b""),
+            });
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &Self
+    where
+        K: Ord,
+        F: FnMut(&[u8]) -> K,
+    {
+        self.pairs.sort_by_key(|pair| f(&pair_key(pair)));
+        self
+    }
+
+    pub fn build(&self) -> Query {
+        let mut bytes = BytesMut::with_capacity(
+            self.pairs.iter().map(|pair| pair.len() + 1).sum::<usize>() + 1,
+        );
+        bytes.put_u8(0x3f);
+        for (index, pair) in self.pairs.iter().enumerate() {
+            if index > 0 {
+                bytes.put_u8(0x26);
+            }
+            bytes.put(&pair[..]);
+        }
+        Query::from_bytes(bytes.freeze())
+    }
+}
+
+fn encode_pair(key: &[u8], value: &[u8]) -> Bytes {
+    let key = percent::encode(key, percent::QUERY_PAIR);
+    let value = percent::encode(value, percent::QUERY_PAIR);
+    let mut bytes = BytesMut::with_capacity(key.len() + value.len() + 1);
+    bytes.put(&key[..]);
+    bytes.put_u8(0x3d);
+    bytes.put(&value[..]);
+    bytes.freeze()
+}
+
+fn pair_key(pair: &[u8]) -> Cow<'_, [u8]> {
+    let key_end = pair
+        .iter()
+        .position(|&char| char == 0x3d)
+        .unwrap_or(pair.len());
+    percent::decode(&pair[..key_end])
+}
+
+#[cfg(test)]
+mod tests_query_builder {
+    use crate::{Query, QueryBuilder};
+    use bytes::Bytes;
+
+    #[test]
+    fn test_build() {
+        let mut query_builder = QueryBuilder::new();
+        query_builder.append(b"name", b"ferret");
+        query_builder.append(b"a&b", b"c=d+e f#");
+        let query = query_builder.build();
+        assert_eq!(
+            query.bytes(),
+            Bytes::from_static(b"?name=ferret&a%26b=c%3Dd%2Be%20f%23")
+        );
+        assert_eq!(query.get(b"a&b").as_deref(), Some(&b"c=d+e f#"[..]));
+        let mut cursor = 0;
+        assert_eq!(
+            Query::parse(&query.origin, &mut cursor, &query.origin.len()).unwrap(),
+            query
+        );
+
+        assert_eq!(
+            QueryBuilder::new().build().bytes(),
+            Bytes::from_static(b"?")
+        );
+    }
+
+    #[test]
+    fn test_from_query_preserves_encoding() {
+        let query = Query::from_slice(b"?b=%7e&&a=x%2fy&flag");
+        let mut query_builder = QueryBuilder::from_query(&query);
+        query_builder.append(b"c", b"~");
+        assert_eq!(
+            query_builder.build().bytes(),
+            Bytes::from_static(b"?b=%7e&a=x%2fy&flag&c=~")
+        );
+    }
+
+    #[test]
+    fn test_set_and_remove() {
+        let query = Query::from_slice(b"?a=1&b=2&%61=3&c=4");
+        let mut query_builder = QueryBuilder::from_query(&query);
+        query_builder.set(b"a", b"5");
+        assert_eq!(
+            query_builder.build().bytes(),
+            Bytes::from_static(b"?a=5&b=2&c=4")
+        );
+        query_builder.set(b"d", b"6");
+        query_builder.remove(b"b");
+        assert_eq!(
+            query_builder.build().bytes(),
+            Bytes::from_static(b"?a=5&c=4&d=6")
+        );
+    }
+
+    #[test]
+    fn test_retain() {
+        let query = Query::from_slice(b"?a=1&b=2&c=%33&flag");
+        let mut query_builder = QueryBuilder::from_query(&query);
+        query_builder.retain(|key, value| key != b"b" && value != b"3");
+        assert_eq!(
+            query_builder.build().bytes(),
+            Bytes::from_static(b"?a=1&flag")
+        );
+    }
+
+    #[test]
+    fn test_sort_by_key() {
+        let query = Query::from_slice(b"?b=1&a=2&c=3&a=1&%62=0");
+        let mut query_builder = QueryBuilder::from_query(&query);
+        query_builder.sort_by_key(|key| key.to_vec());
+        assert_eq!(
+            query_builder.build().bytes(),
+            Bytes::from_static(b"?a=2&a=1&b=1&%62=0&c=3")
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+use crate::percent;
+
+pub type Pair<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>);
+
+#[derive(Debug, Clone)]
+pub struct Pairs<'a> {
+    input: &'a [u8],
+    separators: &'a [u8],
+    form: bool,
+}
+
+impl<'a> Pairs<'a> {
+    pub(crate) fn new(input: &'a [u8], separators: &'a [u8]) -> Self {
+        Self {
+            input,
+            separators,
+            form: false,
+        }
+    }
+
+    pub(crate) fn new_form(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            separators: b"&",
+            form: true,
+        }
+    }
+
+    fn decode(&self, input: &'a [u8]) -> Cow<'a, [u8]> {
+        if self.form && input.contains(&0x2b) {
+            let input: Vec<u8> = input
+                .iter()
+                .map(|&char| if char == 0x2b { 0x20 } else { char })
+                .collect();
+            Cow::Owned(percent::decode(&input).into_owned())
+        } else {
+            percent::decode(input)
+        }
+    }
+}
+
+impl<'a> Iterator for Pairs<'a> {
+    type Item = Pair<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.input.is_empty() {
+                return None;
+            }
+            let pair_end = self
+                .input
+                .iter()
+                .position(|char| self.separators.contains(char))
+                .unwrap_or(self.input.len());
+            let pair = &self.input[..pair_end];
+            self.input = &self.input[(pair_end + 1).min(self.input.len())..];
+            if pair.is_empty() {
+                continue;
+            }
+            let (key, value) = match pair.iter().position(|&char| char == 0x3d) {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => (pair, &pair[pair.len()..]),
+            };
+            return Some((self.decode(key), self.decode(value)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_pairs {
+    use crate::uri::query::pairs::{Pair, Pairs};
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_pairs() {
+        let pairs: Vec<Pair> = Pairs::new(b"a=1&&b=&c&d=x=y&%61=%20", b"&").collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::Borrowed(&b"a"[..]), Cow::Borrowed(&b"1"[..])),
+                (Cow::Borrowed(&b"b"[..]), Cow::Borrowed(&b""[..])),
+                (Cow::Borrowed(&b"c"[..]), Cow::Borrowed(&b""[..])),
+                (Cow::Borrowed(&b"d"[..]), Cow::Borrowed(&b"x=y"[..])),
+                (Cow::Borrowed(&b"a"[..]), Cow::Borrowed(&b" "[..])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pairs_separators() {
+        let pairs: Vec<Pair> = Pairs::new(b"a=1;b=2&c=3", b"&;").collect();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(
+            pairs[1],
+            (Cow::Borrowed(&b"b"[..]), Cow::Borrowed(&b"2"[..]))
+        );
+    }
+
+    #[test]
+    fn test_form_pairs() {
+        let pairs: Vec<Pair> = Pairs::new_form(b"a+b=c+d%2B&e=%20+").collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::Borrowed(&b"a b"[..]), Cow::Borrowed(&b"c d+"[..])),
+                (Cow::Borrowed(&b"e"[..]), Cow::Borrowed(&b"  "[..])),
+            ]
+        );
+
+        let pairs: Vec<Pair> = Pairs::new(b"a+b=c", b"&").collect();
+        assert_eq!(pairs[0].0, Cow::Borrowed(&b"a+b"[..]));
+    }
+}
@@ -0,0 +1,673 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use super::remove_dot_segments;
+use crate::{
+    Authority, Component, Fragment, HierPart, Path, PathKind, Query, Scheme, Uri, UriError,
+    UriErrorKind,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UriReference {
+    pub scheme: Option<Scheme>,
+    pub hier_part: HierPart,
+    pub query: Option<Query>,
+    pub fragment: Option<Fragment>,
+    pub origin: Bytes,
+}
+
+impl UriReference {
+    #[inline]
+    pub fn bytes(&self) -> Bytes {
+        self.origin.clone()
+    }
+
+    #[inline]
+    pub fn is_relative(&self) -> bool {
+        self.scheme.is_none()
+    }
+
+    #[inline]
+    pub fn scheme(&self) -> Option<&Scheme> {
+        self.scheme.as_ref()
+    }
+
+    #[inline]
+    pub fn authority(&self) -> Option<&Authority> {
+        self.hier_part.authority()
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        self.hier_part.path()
+    }
+
+    #[inline]
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+
+    #[inline]
+    pub fn fragment(&self) -> Option<&Fragment> {
+        self.fragment.as_ref()
+    }
+
+    pub fn path_kind(&self) -> PathKind {
+        match self.hier_part.path_kind() {
+            PathKind::Rootless if self.is_relative() => PathKind::NoScheme,
+            path_kind => path_kind,
+        }
+    }
+
+    pub fn from_bytes(input: Bytes) -> Self {
+        let mut scheme_end = 0;
+        if Scheme::scan(&input, &mut scheme_end, &input.len()).is_err()
+            || Scheme::scan_delimiter(&input, &mut scheme_end, &input.len()).is_err()
+        {
+            scheme_end = 0;
+        }
+        let fragment_start = input[scheme_end..]
+            .iter()
+            .position(|&char| char == 0x23)
+            .map_or(input.len(), |index| scheme_end + index);
+        let query_start = input[scheme_end..fragment_start]
+            .iter()
+            .position(|&char| char == 0x3f)
+            .map_or(fragment_start, |index| scheme_end + index);
+        Self {
+            scheme: if scheme_end > 0 {
+                Some(Scheme::from_bytes(input.slice(..scheme_end - 1)))
+            } else {
+                None
+            },
+            hier_part: HierPart::from_bytes(input.slice(scheme_end..query_start)),
+            query: if query_start < fragment_start {
+                Some(Query::from_bytes(input.slice(query_start..fragment_start)))
+            } else {
+                None
+            },
+            fragment: if fragment_start < input.len() {
+                Some(Fragment::from_bytes(input.slice(fragment_start..)))
+            } else {
+                None
+            },
+            origin: input,
+        }
+    }
+
+    #[inline]
+    pub fn from_slice(input: &[u8]) -> Self {
+        let bytes = Bytes::copy_from_slice(input);
+        Self::from_bytes(bytes)
+    }
+
+    #[inline]
+    pub fn parse(input: &str) -> Result<Self, UriError> {
+        Self::parse_bytes(Bytes::copy_from_slice(input.as_bytes()))
+    }
+
+    pub fn parse_bytes(input: Bytes) -> Result<Self, UriError> {
+        let end = input.len();
+        let mut index = 0;
+        let scheme = if Scheme::scan(&input, &mut index, &end).is_ok()
+            && Scheme::scan_delimiter(&input, &mut index, &end).is_ok()
+        {
+            Some(Scheme::from_bytes(input.slice(..index - 1)))
+        } else {
+            index = 0;
+            None
+        };
+        let hier_part = HierPart::parse_bytes(&input, &mut index, &end)?;
+        if scheme.is_none() && hier_part.authority.is_none() {
+            let path = &hier_part.path().origin;
+            let first_segment_end = path
+                .iter()
+                .position(|&char| char == 0x2f)
+                .unwrap_or(path.len());
+            if let Some(colon) = path[..first_segment_end]
+                .iter()
+                .position(|&char| char == 0x3a)
+            {
+                return Err(UriError::new(
+                    UriErrorKind::InvalidPath,
+                    Component::Path,
+                    "segment-nz-nc",
+                )
+                .at(&input, colon));
+            }
+        }
+        let query = if index < end && input[index] == 0x3f {
+            Some(Query::parse_bytes(&input, &mut index, &end)?)
+        } else {
+            None
+        };
+        let fragment = if index < end && input[index] == 0x23 {
+            Some(Fragment::parse_bytes(&input, &mut index, &end)?)
+        } else {
+            None
+        };
+        if index != end {
+            return Err(
+                UriError::new(UriErrorKind::InvalidUri, Component::Uri, "end of URI")
+                    .at(&input, index),
+            );
+        }
+        Ok(Self {
+            scheme,
+            hier_part,
+            query,
+            fragment,
+            origin: input,
+        })
+    }
+}
+
+impl From<Uri> for UriReference {
+    fn from(uri: Uri) -> Self {
+        Self {
+            scheme: Some(uri.scheme),
+            hier_part: uri.hier_part,
+            query: uri.query,
+            fragment: uri.fragment,
+            origin: uri.origin,
+        }
+    }
+}
+
+impl TryFrom<UriReference> for Uri {
+    type Error = UriError;
+
+    fn try_from(uri_reference: UriReference) -> Result<Self, Self::Error> {
+        let scheme = match uri_reference.scheme {
+            Some(scheme) => scheme,
+            None => {
+                return Err(UriError::new(
+                    UriErrorKind::MissingScheme,
+                    Component::Scheme,
+                    "scheme",
+                )
+                .at(&uri_reference.origin, 0));
+            }
+        };
+        Ok(Self {
+            scheme,
+            hier_part: uri_reference.hier_part,
+            query: uri_reference.query,
+            fragment: uri_reference.fragment,
+            origin: uri_reference.origin,
+        })
+    }
+}
+
+impl Uri {
+    pub fn resolve(&self, reference: &UriReference) -> Result<Uri, UriError> {
+        let (scheme, authority, path, query) = match (reference.scheme(), reference.authority()) {
+            (Some(scheme), authority) => (
+                scheme,
+                authority,
+                remove_dot_segments(&reference.path().origin),
+                reference.query(),
+            ),
+            (None, Some(authority)) => (
+                self.scheme(),
+                Some(authority),
+                remove_dot_segments(&reference.path().origin),
+                reference.query(),
+            ),
+            (None, None) => {
+                let reference_path = &reference.path().origin;
+                if reference_path.is_empty() {
+                    (
+                        self.scheme(),
+                        self.authority(),
+                        self.path().origin.to_vec(),
+                        reference.query().or_else(|| self.query()),
+                    )
+                } else if reference_path.starts_with(b"/") {
+                    (
+                        self.scheme(),
+                        self.authority(),
+                        remove_dot_segments(reference_path),
+                        reference.query(),
+                    )
+                } else {
+                    (
+                        self.scheme(),
+                        self.authority(),
+                        remove_dot_segments(&self.merge(reference_path)),
+                        reference.query(),
+                    )
+                }
+            }
+        };
+
+        let mut bytes = BytesMut::new();
+        bytes.put(scheme.bytes());
+        bytes.put_u8(0x3a);
+        match authority {
+            Some(authority) => {
+                bytes.put(&b"//"[..]);
+                bytes.put(authority.bytes());
+            }
+            None if path.starts_with(b"//") => {
+                bytes.put(&b"/."[..]);
+            }
+            None => {}
+        }
+        bytes.put(&path[..]);
+        if let Some(query) = query {
+            bytes.put(query.bytes());
+        }
+        if let Some(fragment) = reference.fragment() {
+            bytes.put(fragment.bytes());
+        }
+        Uri::parse_bytes(bytes.freeze())
+    }
+
+    pub fn make_relative(&self, target: &Uri) -> Option<UriReference> {
+        if self.scheme() != target.scheme() {
+            return None;
+        }
+
+        let bytes = |component: Option<Bytes>| component.unwrap_or_default();
+        let query = bytes(target.query().map(Query::bytes));
+        let fragment = bytes(target.fragment().map(Fragment::bytes));
+        let path = &target.path().origin;
+        let same_authority =
+            self.authority().map(Authority::bytes) == target.authority().map(Authority::bytes);
+
+        let mut candidates: Vec<Vec<u8>> = Vec::new();
+        if same_authority {
+            if self.path().origin == path {
+                if self.query().map(Query::bytes) == target.query().map(Query::bytes) {
+                    candidates.push(fragment.to_vec());
+                } else if target.query().is_some() {
+                    candidates.push([&query[..], &fragment[..]].concat());
+                }
+            }
+            candidates.push([&self.relative_path(path)[..], &query[..], &fragment[..]].concat());
+            if path.starts_with(b"/") && !path.starts_with(b"//") {
+                candidates.push([&path[..], &query[..], &fragment[..]].concat());
+            }
+        }
+        if let Some(authority) = target.authority() {
+            candidates.push([&b"//"[..], &authority.origin, path, &query, &fragment].concat());
+        }
+
+        let after_scheme = |uri: &Uri| uri.origin.slice(uri.scheme.origin.len()..);
+        let target_rest = after_scheme(target);
+        candidates
+            .into_iter()
+            .filter_map(|candidate| UriReference::parse_bytes(Bytes::from(candidate)).ok())
+            .filter(|reference| {
+                matches!(self.resolve(reference), Ok(uri) if after_scheme(&uri) == target_rest)
+            })
+            .min_by_key(|reference| reference.origin.len())
+    }
+
+    fn relative_path(&self, target_path: &[u8]) -> Vec<u8> {
+        let base_path = &self.path().origin;
+        let base_directory = match base_path.iter().rposition(|&char| char == 0x2f) {
+            Some(index) => &base_path[..index],
+            None => &[][..],
+        };
+        let base_segments: Vec<&[u8]> = base_directory.split(|&char| char == 0x2f).collect();
+        let mut target_directories: Vec<&[u8]> = target_path.split(|&char| char == 0x2f).collect();
+        let last_segment = target_directories.pop().unwrap_or_default();
+
+        let common = base_segments
+            .iter()
+            .zip(target_directories.iter())
+            .take_while(|(base, target)| base == target)
+            .count();
+
+        let mut path = Vec::with_capacity(target_path.len());
+        for _ in common..base_segments.len() {
+            path.extend_from_slice(b"../");
+        }
+        for segment in &target_directories[common..] {
+            path.extend_from_slice(segment);
+            path.push(0x2f);
+        }
+        path.extend_from_slice(last_segment);
+
+        let first_segment_end = path
+            .iter()
+            .position(|&char| char == 0x2f)
+            .unwrap_or(path.len());
+        if path.is_empty() || path.starts_with(b"/") || path[..first_segment_end].contains(&0x3a) {
+            path.splice(0..0, b"./".iter().copied());
+        }
+        path
+    }
+
+    fn merge(&self, reference_path: &[u8]) -> Vec<u8> {
+        let base_path = &self.path().origin;
+        let mut path = Vec::with_capacity(base_path.len() + reference_path.len() + 1);
+        if self.authority().is_some() && base_path.is_empty() {
+            path.push(0x2f);
+        } else if let Some(index) = base_path.iter().rposition(|&char| char == 0x2f) {
+            path.extend_from_slice(&base_path[..=index]);
+        }
+        path.extend_from_slice(reference_path);
+        path
+    }
+}
+
+impl FromStr for UriReference {
+    type Err = UriError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<&str> for UriReference {
+    type Error = UriError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        Self::parse(input)
+    }
+}
+
+impl TryFrom<String> for UriReference {
+    type Error = UriError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        Self::parse_bytes(Bytes::from(input))
+    }
+}
+
+impl TryFrom<Bytes> for UriReference {
+    type Error = UriError;
+
+    fn try_from(input: Bytes) -> Result<Self, Self::Error> {
+        Self::parse_bytes(input)
+    }
+}
+
+impl TryFrom<&[u8]> for UriReference {
+    type Error = UriError;
+
+    fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse_bytes(Bytes::copy_from_slice(input))
+    }
+}
+
+impl fmt::Display for UriReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.origin))
+    }
+}
+
+#[cfg(test)]
+mod tests_uri_reference {
+    use crate::{
+        Authority, Fragment, Path, PathKind, Query, Scheme, Uri, UriErrorKind, UriReference,
+    };
+    use bytes::Bytes;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_from_slice() {
+        let uri_reference = UriReference::from_slice(b"../a/b?x#y");
+        assert_eq!(uri_reference.scheme(), None);
+        assert_eq!(uri_reference.path(), &Path::from_slice(b"../a/b"));
+        assert_eq!(uri_reference.query(), Some(&Query::from_slice(b"?x")));
+        assert_eq!(uri_reference.fragment(), Some(&Fragment::from_slice(b"#y")));
+    }
+
+    #[test]
+    fn test_parse_absolute() {
+        let uri_reference =
+            UriReference::parse("foo://example.com:8042/over/there?name=ferret#nose").unwrap();
+        assert!(!uri_reference.is_relative());
+        assert_eq!(uri_reference.scheme(), Some(&Scheme::from_slice(b"foo")));
+        assert_eq!(
+            uri_reference.authority(),
+            Some(&Authority::from_slice(b"example.com:8042"))
+        );
+        assert_eq!(uri_reference.path_kind(), PathKind::AbEmpty);
+
+        let uri_reference = UriReference::parse("a:b").unwrap();
+        assert_eq!(uri_reference.scheme(), Some(&Scheme::from_slice(b"a")));
+        assert_eq!(uri_reference.path(), &Path::from_slice(b"b"));
+        assert_eq!(uri_reference.path_kind(), PathKind::Rootless);
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        let uri_reference = UriReference::parse("../a/b?x#y").unwrap();
+        assert!(uri_reference.is_relative());
+        assert_eq!(uri_reference.authority(), None);
+        assert_eq!(uri_reference.path(), &Path::from_slice(b"../a/b"));
+        assert_eq!(uri_reference.path_kind(), PathKind::NoScheme);
+        assert_eq!(uri_reference.query(), Some(&Query::from_slice(b"?x")));
+        assert_eq!(uri_reference.fragment(), Some(&Fragment::from_slice(b"#y")));
+
+        let uri_reference = UriReference::parse("//cdn.example.com/x.js").unwrap();
+        assert_eq!(uri_reference.scheme(), None);
+        assert_eq!(
+            uri_reference.authority(),
+            Some(&Authority::from_slice(b"cdn.example.com"))
+        );
+        assert_eq!(uri_reference.path(), &Path::from_slice(b"/x.js"));
+        assert_eq!(uri_reference.path_kind(), PathKind::AbEmpty);
+
+        let uri_reference = UriReference::parse("/over/there").unwrap();
+        assert_eq!(uri_reference.path_kind(), PathKind::Absolute);
+
+        let uri_reference = UriReference::parse("./this:that").unwrap();
+        assert_eq!(uri_reference.path(), &Path::from_slice(b"./this:that"));
+        assert_eq!(uri_reference.path_kind(), PathKind::NoScheme);
+
+        let uri_reference = UriReference::parse("g/h:i").unwrap();
+        assert_eq!(uri_reference.path_kind(), PathKind::NoScheme);
+
+        let uri_reference = UriReference::parse("?y").unwrap();
+        assert_eq!(uri_reference.path_kind(), PathKind::Empty);
+        assert_eq!(uri_reference.query(), Some(&Query::from_slice(b"?y")));
+
+        let uri_reference = UriReference::parse("#s").unwrap();
+        assert_eq!(uri_reference.fragment(), Some(&Fragment::from_slice(b"#s")));
+
+        let uri_reference = UriReference::parse("").unwrap();
+        assert_eq!(uri_reference.path_kind(), PathKind::Empty);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for string in &["1this:that", "-a:b", "../a b", "//exa mple.com/", "a#b#c"] {
+            assert!(UriReference::parse(string).is_err());
+        }
+        assert!(matches!(
+            UriReference::parse("1this:that"),
+            Err(error) if error.kind() == UriErrorKind::InvalidPath && error.offset() == 5
+        ));
+    }
+
+    #[test]
+    fn test_uri_conversion() {
+        let uri = Uri::parse("http://example.com/over/there").unwrap();
+        let uri_reference = UriReference::from(uri.clone());
+        assert_eq!(
+            uri_reference,
+            UriReference::parse("http://example.com/over/there").unwrap()
+        );
+        assert_eq!(Uri::try_from(uri_reference).unwrap(), uri);
+
+        let uri_reference = UriReference::parse("/over/there").unwrap();
+        assert!(matches!(
+            Uri::try_from(uri_reference),
+            Err(error) if error.kind() == UriErrorKind::MissingScheme && error.byte() == Some(b'/')
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        let string = "//example.com/over/there?name=ferret#nose";
+        let uri_reference: UriReference = string.parse().unwrap();
+        assert_eq!(uri_reference.to_string(), string);
+        assert_eq!(
+            UriReference::try_from(Bytes::from(string)).unwrap(),
+            uri_reference
+        );
+    }
+
+    fn resolve(base: &Uri, reference: &str) -> String {
+        base.resolve(&UriReference::parse(reference).unwrap())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_resolve_normal() {
+        let base = Uri::parse("http://a/b/c/d;p?q").unwrap();
+        for (reference, target) in &[
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("g;x", "http://a/b/c/g;x"),
+            ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../", "http://a/"),
+            ("../../g", "http://a/g"),
+        ] {
+            assert_eq!(&resolve(&base, reference), target, "{}", reference);
+        }
+    }
+
+    #[test]
+    fn test_resolve_abnormal() {
+        let base = Uri::parse("http://a/b/c/d;p?q").unwrap();
+        for (reference, target) in &[
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g?y/../x", "http://a/b/c/g?y/../x"),
+            ("g#s/./x", "http://a/b/c/g#s/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+            ("http:g", "http:g"),
+        ] {
+            assert_eq!(&resolve(&base, reference), target, "{}", reference);
+        }
+    }
+
+    #[test]
+    fn test_resolve_empty_base_path() {
+        let base = Uri::parse("http://a").unwrap();
+        assert_eq!(resolve(&base, "g"), "http://a/g");
+
+        let base = Uri::parse("foo:a").unwrap();
+        assert_eq!(resolve(&base, "b"), "foo:b");
+        assert_eq!(resolve(&base, "/.//g"), "foo:/.//g");
+    }
+
+    fn make_relative(base: &str, target: &str) -> Option<String> {
+        let base = Uri::parse(base).unwrap();
+        let target = Uri::parse(target).unwrap();
+        let reference = base.make_relative(&target)?;
+        assert_eq!(base.resolve(&reference).unwrap(), target);
+        Some(reference.to_string())
+    }
+
+    #[test]
+    fn test_make_relative() {
+        let base = "http://a/b/c/d;p?q";
+        for (target, reference) in &[
+            ("http://a/b/c/g", "g"),
+            ("http://a/b/c/g/", "g/"),
+            ("http://a/b/c/", "./"),
+            ("http://a/b/", "../"),
+            ("http://a/b/g", "../g"),
+            ("http://a/", "/"),
+            ("http://a/g", "/g"),
+            ("http://a/b/c/d;p?y", "?y"),
+            ("http://a/b/c/g?y#s", "g?y#s"),
+            ("http://a/b/c/d;p?q#s", "#s"),
+            ("http://a/b/c/d;p?q", ""),
+            ("http://a/b/c/d;p", "d;p"),
+            ("http://a/b/c/g:h", "./g:h"),
+            ("http://g/x", "//g/x"),
+            ("http://a/b/c//x", ".//x"),
+        ] {
+            assert_eq!(
+                make_relative(base, target).as_deref(),
+                Some(*reference),
+                "{}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_make_relative_same_path() {
+        assert_eq!(
+            make_relative("http://a/b/", "http://a/b/").as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            make_relative("http://a/b/?q", "http://a/b/").as_deref(),
+            Some("./")
+        );
+        assert_eq!(
+            make_relative("http://a/b/c?q#f", "http://a/b/c#g").as_deref(),
+            Some("c#g")
+        );
+        assert_eq!(
+            make_relative("http://a", "http://a#top").as_deref(),
+            Some("#top")
+        );
+        assert_eq!(
+            make_relative("http://a?q", "http://a").as_deref(),
+            Some("//a")
+        );
+    }
+
+    #[test]
+    fn test_make_relative_scheme_case() {
+        let base = Uri::parse("HTTP://a/b/c").unwrap();
+        let target = Uri::parse("http://a/b/d").unwrap();
+        let reference = base.make_relative(&target).unwrap();
+        assert_eq!(reference.to_string(), "d");
+        assert_eq!(
+            base.resolve(&reference).unwrap().to_string(),
+            "HTTP://a/b/d"
+        );
+    }
+
+    #[test]
+    fn test_make_relative_impossible() {
+        assert_eq!(make_relative("http://a/b", "https://a/b"), None);
+        assert_eq!(make_relative("http://a/b", "http:/b"), None);
+    }
+}
@@ -1,13 +1,98 @@
 use bytes::Bytes;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-use crate::UriError;
+use crate::{is_alpha, is_scheme_char, Component, Port, UriError, UriErrorKind};
 
-#[derive(Debug, Clone, PartialEq)]
+const DEFAULT_PORTS: [(&[u8], u16); 12] = [
+    (b"http", 80),
+    (b"https", 443),
+    (b"ws", 80),
+    (b"wss", 443),
+    (b"ftp", 21),
+    (b"ssh", 22),
+    (b"telnet", 23),
+    (b"gopher", 70),
+    (b"ldap", 389),
+    (b"ldaps", 636),
+    (b"nntp", 119),
+    (b"rtsp", 554),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownScheme {
+    Http,
+    Https,
+    Ws,
+    Wss,
+    Ftp,
+    File,
+    Mailto,
+    Data,
+    Urn,
+    Tel,
+}
+
+const KNOWN_SCHEMES: [KnownScheme; 10] = [
+    KnownScheme::Http,
+    KnownScheme::Https,
+    KnownScheme::Ws,
+    KnownScheme::Wss,
+    KnownScheme::Ftp,
+    KnownScheme::File,
+    KnownScheme::Mailto,
+    KnownScheme::Data,
+    KnownScheme::Urn,
+    KnownScheme::Tel,
+];
+
+impl KnownScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KnownScheme::Http => "http",
+            KnownScheme::Https => "https",
+            KnownScheme::Ws => "ws",
+            KnownScheme::Wss => "wss",
+            KnownScheme::Ftp => "ftp",
+            KnownScheme::File => "file",
+            KnownScheme::Mailto => "mailto",
+            KnownScheme::Data => "data",
+            KnownScheme::Urn => "urn",
+            KnownScheme::Tel => "tel",
+        }
+    }
+}
+
+impl From<KnownScheme> for Scheme {
+    fn from(known_scheme: KnownScheme) -> Self {
+        Self::from_bytes(Bytes::from_static(known_scheme.as_str().as_bytes()))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Scheme {
     pub origin: Bytes,
 }
 
 impl Scheme {
+    pub const HTTP: Scheme = Scheme::from_static(b"http");
+    pub const HTTPS: Scheme = Scheme::from_static(b"https");
+    pub const WS: Scheme = Scheme::from_static(b"ws");
+    pub const WSS: Scheme = Scheme::from_static(b"wss");
+    pub const FTP: Scheme = Scheme::from_static(b"ftp");
+    pub const FILE: Scheme = Scheme::from_static(b"file");
+    pub const MAILTO: Scheme = Scheme::from_static(b"mailto");
+    pub const DATA: Scheme = Scheme::from_static(b"data");
+    pub const URN: Scheme = Scheme::from_static(b"urn");
+    pub const TEL: Scheme = Scheme::from_static(b"tel");
+
+    const fn from_static(input: &'static [u8]) -> Self {
+        Self {
+            origin: Bytes::from_static(input),
+        }
+    }
+
     #[inline]
     pub fn bytes(&self) -> Bytes {
         self.origin.clone()
@@ -24,42 +109,169 @@ impl Scheme {
         Self::from_bytes(bytes)
     }
 
-    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+    pub fn known(&self) -> Option<KnownScheme> {
+        KNOWN_SCHEMES
+            .iter()
+            .find(|known_scheme| {
+                known_scheme
+                    .as_str()
+                    .as_bytes()
+                    .eq_ignore_ascii_case(&self.origin)
+            })
+            .copied()
+    }
+
+    pub fn default_port(&self) -> Option<u16> {
+        DEFAULT_PORTS
+            .iter()
+            .find(|(scheme, _)| scheme.eq_ignore_ascii_case(&self.origin))
+            .map(|(_, port)| *port)
+    }
+
+    pub(crate) fn scan(input: &[u8], start: &mut usize, end: &usize) -> Result<(), UriError> {
         let mut index = *start;
-        while index < *end && input[index] != 0x3a {
+        if index >= *end || !is_alpha!(input[index]) {
+            return Err(
+                UriError::new(UriErrorKind::InvalidScheme, Component::Scheme, "ALPHA")
+                    .at(input, index),
+            );
+        }
+        index += 1;
+        while index < *end && is_scheme_char!(input[index]) {
             index += 1;
         }
-        if input[index] != 0x3a {
-            return Err(UriError::InvalidScheme);
+        *start = index;
+        Ok(())
+    }
+
+    pub(crate) fn scan_delimiter(
+        input: &[u8],
+        start: &mut usize,
+        end: &usize,
+    ) -> Result<(), UriError> {
+        if *start >= *end || input[*start] != 0x3a {
+            return Err(UriError::new(
+                UriErrorKind::InvalidScheme,
+                Component::Scheme,
+                "ALPHA, DIGIT, \"+\", \"-\", \".\" or \":\"",
+            )
+            .at(input, *start));
         }
-        index += 1;
+        *start += 1;
+        Ok(())
+    }
+
+    pub fn parse(input: &[u8], start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
         let value = Self::from_slice(&input[*start..index]);
+        Self::scan_delimiter(input, &mut index, end)?;
         *start = index;
         Ok(value)
     }
+
+    pub fn parse_bytes(input: &Bytes, start: &mut usize, end: &usize) -> Result<Self, UriError> {
+        let mut index = *start;
+        Self::scan(input, &mut index, end)?;
+        let value = Self::from_bytes(input.slice(*start..index));
+        Self::scan_delimiter(input, &mut index, end)?;
+        *start = index;
+        Ok(value)
+    }
+}
+
+impl PartialEq for Scheme {
+    fn eq(&self, other: &Self) -> bool {
+        self.origin.eq_ignore_ascii_case(&other.origin)
+    }
+}
+
+impl Eq for Scheme {}
+
+impl Hash for Scheme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for char in self.origin.iter() {
+            state.write_u8(char.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl PartialEq<KnownScheme> for Scheme {
+    fn eq(&self, other: &KnownScheme) -> bool {
+        other.as_str().as_bytes().eq_ignore_ascii_case(&self.origin)
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = UriError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let bytes = Bytes::copy_from_slice(input.as_bytes());
+        let mut index = 0;
+        Self::scan(&bytes, &mut index, &bytes.len())?;
+        if index != bytes.len() {
+            return Err(UriError::new(
+                UriErrorKind::InvalidScheme,
+                Component::Scheme,
+                "ALPHA, DIGIT, \"+\", \"-\" or \".\"",
+            )
+            .at(&bytes, index));
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+impl TryFrom<&str> for Scheme {
+    type Error = UriError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+impl TryFrom<Port> for Scheme {
+    type Error = UriError;
+
+    fn try_from(port: Port) -> Result<Self, Self::Error> {
+        DEFAULT_PORTS
+            .iter()
+            .find(|(_, value)| Some(*value) == port.value)
+            .map(|(name, _)| Scheme::from_static(name))
+            .ok_or_else(|| {
+                UriError::new(
+                    UriErrorKind::UnknownScheme,
+                    Component::Port,
+                    "a port with a registered scheme",
+                )
+                .at(&port.origin, 0)
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests_scheme {
-    use crate::Scheme;
+    use crate::{KnownScheme, Port, Scheme, UriErrorKind};
     use bytes::Bytes;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
 
     #[test]
     fn test_bytes() {
-        let scheme = Scheme::from_bytes(Bytes::from_static(b"foo:"));
-        assert_eq!(scheme.bytes(), Bytes::from_static(b"foo:"));
+        let scheme = Scheme::from_bytes(Bytes::from_static(b"foo"));
+        assert_eq!(scheme.bytes(), Bytes::from_static(b"foo"));
     }
 
     #[test]
     fn test_from_bytes() {
-        let scheme = Scheme::from_bytes(Bytes::from_static(b"foo:"));
-        assert_eq!(scheme.origin, Bytes::from_static(b"foo:"));
+        let scheme = Scheme::from_bytes(Bytes::from_static(b"foo"));
+        assert_eq!(scheme.origin, Bytes::from_static(b"foo"));
     }
 
     #[test]
     fn test_from_slice() {
-        let scheme = Scheme::from_slice(b"foo:");
-        assert_eq!(scheme.origin, Bytes::from_static(b"foo:"));
+        let scheme = Scheme::from_slice(b"foo");
+        assert_eq!(scheme.origin, Bytes::from_static(b"foo"));
     }
 
     #[test]
@@ -67,7 +279,92 @@ mod tests_scheme {
         let string = "foo://example.com:8042/over/there?name=ferret#nose";
         let mut cursor = 0;
         let scheme = Scheme::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
-        assert_eq!(scheme.origin, Bytes::from_static(b"foo:"));
+        assert_eq!(scheme.origin, Bytes::from_static(b"foo"));
         assert_eq!(cursor, 4);
+
+        let string = "svn+ssh.v2-x:";
+        let mut cursor = 0;
+        let scheme = Scheme::parse(string.as_bytes(), &mut cursor, &string.len()).unwrap();
+        assert_eq!(scheme.origin, Bytes::from_static(b"svn+ssh.v2-x"));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for string in &[
+            "ht tp://example.com",
+            "1http://example.com",
+            "+http:",
+            "http",
+            "",
+        ] {
+            let mut cursor = 0;
+            assert!(Scheme::parse(string.as_bytes(), &mut cursor, &string.len()).is_err());
+            assert_eq!(cursor, 0);
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("HTTP".parse::<Scheme>().unwrap(), Scheme::HTTP);
+        assert_eq!(
+            Scheme::try_from("coap+tcp").unwrap(),
+            Scheme::from_slice(b"coap+tcp")
+        );
+        for string in &["http:", "ht tp", "1http", "", "http_s"] {
+            assert!(string.parse::<Scheme>().is_err(), "{}", string);
+        }
+        let error = "ht tp".parse::<Scheme>().unwrap_err();
+        assert_eq!(error.kind(), UriErrorKind::InvalidScheme);
+        assert_eq!(error.offset(), 2);
+    }
+
+    #[test]
+    fn test_eq_ignore_case() {
+        assert_eq!(Scheme::from_slice(b"HTTP"), Scheme::from_slice(b"http"));
+        assert_eq!(Scheme::from_slice(b"HtTpS"), Scheme::HTTPS);
+        assert_ne!(Scheme::HTTP, Scheme::HTTPS);
+        assert_eq!(
+            Scheme::from_slice(b"HTTP").bytes(),
+            Bytes::from_static(b"HTTP")
+        );
+
+        let schemes: HashSet<Scheme> = vec![Scheme::from_slice(b"HTTP"), Scheme::HTTP]
+            .into_iter()
+            .collect();
+        assert_eq!(schemes.len(), 1);
+    }
+
+    #[test]
+    fn test_known() {
+        assert_eq!(
+            Scheme::from_slice(b"MailTo").known(),
+            Some(KnownScheme::Mailto)
+        );
+        assert_eq!(Scheme::TEL.known(), Some(KnownScheme::Tel));
+        assert_eq!(Scheme::from_slice(b"foo").known(), None);
+        assert_eq!(Scheme::from(KnownScheme::Urn), Scheme::URN);
+        assert_eq!(Scheme::from_slice(b"FILE"), KnownScheme::File);
+        assert_eq!(KnownScheme::Data.as_str(), "data");
+    }
+
+    #[test]
+    fn test_default_port() {
+        assert_eq!(Scheme::from_slice(b"http").default_port(), Some(80));
+        assert_eq!(Scheme::from_slice(b"HTTPS").default_port(), Some(443));
+        assert_eq!(Scheme::WSS.default_port(), Some(443));
+        assert_eq!(Scheme::FTP.default_port(), Some(21));
+        assert_eq!(Scheme::from_slice(b"foo").default_port(), None);
+    }
+
+    #[test]
+    fn test_port_to_scheme() {
+        let scheme = Scheme::try_from(Port::from(443)).unwrap();
+        assert_eq!(scheme, Scheme::HTTPS);
+        assert_eq!(scheme.bytes(), Bytes::from_static(b"https"));
+
+        assert!(matches!(
+            Scheme::try_from(Port::from(8042)),
+            Err(error) if error.kind() == UriErrorKind::UnknownScheme
+        ));
     }
 }
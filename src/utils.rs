@@ -1,258 +1,301 @@
-// use std::error::Error;
-
-// use crate::{is_hexdig, is_sub_delims, is_unreserved};
-
-// #[derive(Debug, Clone)]
-// pub enum PctEncodedError {
-//     InvalidValue,
-// }
-
-// impl std::fmt::Display for PctEncodedError {
-//     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-//         write!(f, "invalid value")
-//     }
-// }
-
-// impl Error for PctEncodedError {}
-
-// pub fn while_pct_encoded(
-//     input: &[u8],
-//     start: &mut usize,
-//     end: &usize,
-// ) -> Result<bool, Box<dyn Error>> {
-//     let mut index = *start;
-//     let flag = input[index] == 0x25;
-
-//     while index <= *end && input[index] == 0x25 {
-//         if index + 2 > *end || !is_hexdig!(input[index + 1]) || !is_hexdig!(input[index + 2]) {
-//             return Err(PctEncodedError::InvalidValue.into());
-//         }
-//         index += 3;
-//     }
-
-//     if flag {
-//         *start = index - 1;
-//     }
-
-//     Ok(flag)
-// }
-
-// pub fn while_pchar(input: &[u8], start: &mut usize, end: &usize) -> Result<bool, Box<dyn Error>> {
-//     let mut index = *start;
-//     let flag = is_unreserved!(input[index])
-//         || while_pct_encoded(input, &mut index, end)?
-//         || is_sub_delims!(input[index])
-//         || input[index] == 0x3a
-//         || input[index] == 0x40;
-
-//     while index < *end
-//         && (is_unreserved!(input[index])
-//             || while_pct_encoded(input, &mut index, end)?
-//             || is_sub_delims!(input[index])
-//             || input[index] == 0x3a
-//             || input[index] == 0x40)
-//     {
-//         index += 1;
-//     }
-
-//     Ok(flag)
-// }
-
-// pub fn while_ip_v4_address(
-//     input: &[u8],
-//     start: &mut usize,
-//     end: &usize,
-// ) -> Result<bool, Box<dyn Error>> {
-//     if *end - *start < 6 || *end - *start > 14 {
-//         return Ok(false);
-//     }
-
-//     let mut index = *start;
-//     let mut dec_octet_count = 0;
-//     let mut dec_octet_index = index;
-
-//     while dec_octet_count < 4 {
-//         while dec_octet_index <= *end
-//             && dec_octet_index - index < 4
-//             && input[dec_octet_index] != 0x2e
-//             && input[dec_octet_index] >= 0x30
-//             && input[dec_octet_index] <= 0x39
-//         {
-//             dec_octet_index += 1;
-//         }
-
-//         match dec_octet_index - index {
-//             3 => {
-//                 if !((input[index] == 0x31
-//                     && input[index + 1] >= 0x30
-//                     && input[index + 1] <= 0x39
-//                     && input[index + 2] >= 0x30
-//                     && input[index + 2] <= 0x39)
-//                     || (input[index] == 0x32
-//                         && input[index + 1] >= 0x30
-//                         && input[index + 1] <= 0x35
-//                         && input[index + 2] >= 0x30
-//                         && input[index + 2] <= 0x35))
-//                 {
-//                     return Ok(false);
-//                 }
-//             }
-//             2 => {
-//                 if input[index] < 0x31
-//                     || input[index] > 0x39
-//                     || input[index + 1] < 0x30
-//                     || input[index + 1] > 0x39
-//                 {
-//                     return Ok(false);
-//                 }
-//             }
-//             1 => {
-//                 if input[index] < 0x30 || input[index] > 0x39 {
-//                     return Ok(false);
-//                 }
-//             }
-//             _ => return Ok(false),
-//         }
-
-//         dec_octet_index += 1;
-//         index = dec_octet_index;
-//         dec_octet_count += 1;
-//     }
-
-//     *start = dec_octet_index;
-
-//     Ok(true)
-// }
-
-// #[cfg(test)]
-// mod test_while_ip_v4_address {
-//     use crate::utils::while_ip_v4_address;
-
-//     #[test]
-//     fn invalid_length_short() {
-//         let string = b"0.0.0.";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             false
-//         );
-//         assert_eq!(cursor, 0);
-//     }
-
-//     #[test]
-//     fn invalid_length_long() {
-//         let string = b"255.255.255.2550";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             false
-//         );
-//         assert_eq!(cursor, 0);
-//     }
-
-//     #[test]
-//     fn invalid_dec_octet_length_short() {
-//         let string = b"255.255.255.";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             false
-//         );
-//         assert_eq!(cursor, 0);
-//     }
-
-//     #[test]
-//     fn invalid_dec_octet_length_long() {
-//         let string = b"255.255.0.2555";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             false
-//         );
-//         assert_eq!(cursor, 0);
-//     }
-
-//     #[test]
-//     fn invalid_dec_octet_value() {
-//         let string = b"255.255.255.256";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             false
-//         );
-//         assert_eq!(cursor, 0);
-//     }
-
-//     #[test]
-//     fn valid() {
-//         let string = b"0.10.200.200:80";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-//         assert_eq!(
-//             while_ip_v4_address(string, &mut cursor, &end).unwrap(),
-//             true
-//         );
-//         // assert_eq!(cursor, 12);
-//     }
-// }
-
-// pub fn while_number(input: &[u8], start: &mut usize, end: &usize) -> Result<bool, Box<dyn Error>> {
-//     let mut index = *start;
-
-//     if index > *end || input[index] < 0x31 || input[index] > 0x39 {
-//         return Ok(false);
-//     }
-
-//     index += 1;
-
-//     while index <= *end && input[index] >= 0x30 && input[index] <= 0x39 {
-//         index += 1;
-//     }
-
-//     *start = index;
-
-//     return Ok(true);
-// }
-
-// #[cfg(test)]
-// mod test_while_number {
-//     use crate::utils::while_number;
-
-//     #[test]
-//     fn valid() {
-//         let string = b"80/";
-//         let mut cursor = 0;
-//         let end = string.len() - 1;
-
-//         assert_eq!(while_number(string, &mut cursor, &end).unwrap(), true);
-//         assert_eq!(cursor, 2);
-//     }
-// }
-
-// pub fn while_reg_name(
-//     input: &[u8],
-//     start: &mut usize,
-//     end: &usize,
-// ) -> Result<bool, Box<dyn Error>> {
-//     let mut index = *start;
-
-//     while index <= *end
-//         && (is_unreserved!(input[index])
-//             || while_pct_encoded(input, &mut index, end)?
-//             || is_sub_delims!(input[index]))
-//     {
-//         index += 1;
-//     }
-
-//     Ok(if index != *start {
-//         *start = index;
-//         true
-//     } else {
-//         false
-//     })
-// }
+use crate::{is_digit, is_hexdig, is_pchar, is_sub_delims, is_unreserved};
+
+#[inline]
+pub fn is_pct_encoded(input: &[u8], index: usize, end: usize) -> bool {
+    index + 2 < end
+        && input[index] == 0x25
+        && is_hexdig!(input[index + 1])
+        && is_hexdig!(input[index + 2])
+}
+
+pub fn while_pct_encoded(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while is_pct_encoded(input, index, *end) {
+        index += 3;
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_pchar(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end {
+        if is_pchar!(input[index]) {
+            index += 1;
+        } else if !while_pct_encoded(input, &mut index, end) {
+            break;
+        }
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_path(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end {
+        if input[index] == 0x2f {
+            index += 1;
+        } else if !while_pchar(input, &mut index, end) {
+            break;
+        }
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_query_or_fragment(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end {
+        if input[index] == 0x2f || input[index] == 0x3f {
+            index += 1;
+        } else if !while_pchar(input, &mut index, end) {
+            break;
+        }
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_userinfo(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end {
+        if is_unreserved!(input[index]) || is_sub_delims!(input[index]) || input[index] == 0x3a {
+            index += 1;
+        } else if !while_pct_encoded(input, &mut index, end) {
+            break;
+        }
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_reg_name(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end {
+        if is_unreserved!(input[index]) || is_sub_delims!(input[index]) {
+            index += 1;
+        } else if !while_pct_encoded(input, &mut index, end) {
+            break;
+        }
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_digit(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end && is_digit!(input[index]) {
+        index += 1;
+    }
+    let flag = index != *start;
+    *start = index;
+    flag
+}
+
+pub fn while_dec_octet(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    while index < *end && index - *start < 3 && is_digit!(input[index]) {
+        index += 1;
+    }
+    let valid = match index - *start {
+        1 => true,
+        2 => input[*start] != 0x30,
+        3 => {
+            input[*start] == 0x31
+                || (input[*start] == 0x32
+                    && (input[*start + 1] <= 0x34
+                        || (input[*start + 1] == 0x35 && input[*start + 2] <= 0x35)))
+        }
+        _ => false,
+    };
+    if valid && !(index < *end && is_digit!(input[index])) {
+        *start = index;
+        true
+    } else {
+        false
+    }
+}
+
+pub fn while_ip_v4_address(input: &[u8], start: &mut usize, end: &usize) -> bool {
+    let mut index = *start;
+    for dec_octet_count in 0..4 {
+        if dec_octet_count > 0 {
+            if index >= *end || input[index] != 0x2e {
+                return false;
+            }
+            index += 1;
+        }
+        if !while_dec_octet(input, &mut index, end) {
+            return false;
+        }
+    }
+    *start = index;
+    true
+}
+
+pub fn normalize_pct_encoded(input: &[u8], output: &mut Vec<u8>, lowercase: bool) {
+    let mut index = 0;
+    while index < input.len() {
+        if is_pct_encoded(input, index, input.len()) {
+            let char = (hex_value(input[index + 1]) << 4) | hex_value(input[index + 2]);
+            if is_unreserved!(char) {
+                output.push(if lowercase {
+                    char.to_ascii_lowercase()
+                } else {
+                    char
+                });
+            } else {
+                output.push(0x25);
+                output.push(input[index + 1].to_ascii_uppercase());
+                output.push(input[index + 2].to_ascii_uppercase());
+            }
+            index += 3;
+        } else {
+            output.push(if lowercase {
+                input[index].to_ascii_lowercase()
+            } else {
+                input[index]
+            });
+            index += 1;
+        }
+    }
+}
+
+#[inline]
+pub fn hex_value(char: u8) -> u8 {
+    match char {
+        0x30..=0x39 => char - 0x30,
+        0x41..=0x46 => char - 0x37,
+        0x61..=0x66 => char - 0x57,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests_while_pct_encoded {
+    use crate::utils::while_pct_encoded;
+
+    #[test]
+    fn test_valid() {
+        let string = b"%20%7Eabc";
+        let mut cursor = 0;
+        assert!(while_pct_encoded(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 6);
+    }
+
+    #[test]
+    fn test_invalid_hexdig() {
+        let string = b"%zz";
+        let mut cursor = 0;
+        assert!(!while_pct_encoded(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let string = b"%2";
+        let mut cursor = 0;
+        assert!(!while_pct_encoded(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_while_pchar {
+    use crate::utils::while_pchar;
+
+    #[test]
+    fn test_valid() {
+        let string = b"a-b.c_d~e!$&'()*+,;=:@%41/next";
+        let mut cursor = 0;
+        assert!(while_pchar(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 25);
+    }
+
+    #[test]
+    fn test_stops_on_invalid() {
+        let string = b"ab cd";
+        let mut cursor = 0;
+        assert!(while_pchar(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn test_empty() {
+        let string = b"/path";
+        let mut cursor = 0;
+        assert!(!while_pchar(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_while_ip_v4_address {
+    use crate::utils::while_ip_v4_address;
+
+    #[test]
+    fn test_invalid_length_short() {
+        let string = b"0.0.0.";
+        let mut cursor = 0;
+        assert!(!while_ip_v4_address(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn test_invalid_dec_octet_length_long() {
+        let string = b"255.255.0.2555";
+        let mut cursor = 0;
+        assert!(!while_ip_v4_address(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn test_invalid_dec_octet_value() {
+        for string in &[&b"255.255.255.256"[..], b"255.255.260.1", b"300.1.1.1"] {
+            let mut cursor = 0;
+            assert!(!while_ip_v4_address(string, &mut cursor, &string.len()));
+            assert_eq!(cursor, 0);
+        }
+    }
+
+    #[test]
+    fn test_invalid_leading_zero() {
+        let string = b"10.01.0.1";
+        let mut cursor = 0;
+        assert!(!while_ip_v4_address(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn test_valid() {
+        let string = b"0.10.200.255:80";
+        let mut cursor = 0;
+        assert!(while_ip_v4_address(string, &mut cursor, &string.len()));
+        assert_eq!(cursor, 12);
+    }
+}
+
+#[cfg(test)]
+mod tests_normalize_pct_encoded {
+    use crate::utils::normalize_pct_encoded;
+
+    #[test]
+    fn test_normalize() {
+        let mut output = Vec::new();
+        normalize_pct_encoded(b"%7euser/%2f%41%3a", &mut output, false);
+        assert_eq!(output, b"~user/%2FA%3A");
+    }
+
+    #[test]
+    fn test_normalize_lowercase() {
+        let mut output = Vec::new();
+        normalize_pct_encoded(b"Example.COM%2e%2f%41", &mut output, true);
+        assert_eq!(output, b"example.com.%2Fa");
+    }
+}